@@ -1,11 +1,12 @@
 use eframe::CreationContext;
-use egui::{Context, LayerId, SelectableLabel, Ui, Visuals, Window};
+use egui::{Context, Key, LayerId, SelectableLabel, Ui, Visuals, Window};
 
 use crate::{
     graph::{Graph, NodeIndex},
+    history::GraphEdit,
     menus::{menus, Menu, MENU_COUNT},
     painter::GraphPainter,
-    traversers::TraversalManager,
+    traversers::{all_pairs::distance_matrix_ui, TraversalManager},
 };
 
 pub const INTERNAL_WIDTH: f32 = 1000.0;
@@ -35,7 +36,8 @@ pub struct GraphApp {
     pub painter: GraphPainter,
     pub graphs: Vec<Graph>,
     pub curr_graph: usize,
-    pub curr_drag: Option<NodeIndex>,
+    /// The node being dragged and where it was when the drag started
+    pub curr_drag: Option<(NodeIndex, (f32, f32))>,
     pub traversal_manager: TraversalManager,
 }
 
@@ -46,14 +48,41 @@ impl GraphApp {
 
         Window::new("Graph Visualizer").show(ctx, |ui| menus.draw(ui, self));
 
-        let painter = ctx.layer_painter(LayerId::background());
+        // The traversal holds onto nodes from the graph it was started on, so it can't keep going
+        // once we've moved to a different one
+        if self.curr_graph != curr_graph || len != self.graphs.len() {
+            if self.traversal_manager.traversal.is_some() && len <= self.graphs.len() {
+                if let Some(graph) = self.graphs.get_mut(curr_graph) {
+                    graph.reset();
+                }
+            }
+
+            self.traversal_manager.stop_traversal();
+            menus.graph_updated(self);
+        }
 
         if let Some(graph) = self.graphs.get(self.curr_graph) {
-            self.painter.paint_graph(graph, &painter);
+            let manager = &mut self.traversal_manager;
+
+            if let Some(matrix) = manager.traversal.as_ref().and_then(|t| t.distance_matrix()) {
+                Window::new("Distance Matrix").show(ctx, |ui| {
+                    distance_matrix_ui(ui, graph, matrix, &mut manager.selected_pair)
+                });
+            }
+        }
+
+        let mut painter = ctx.layer_painter(LayerId::background());
+
+        if let Some(graph) = self.graphs.get(self.curr_graph) {
+            self.painter.paint_graph(graph, &mut painter);
+
+            if let Some(path) = self.traversal_manager.selected_path() {
+                self.painter.paint_node_path(&path, graph, &mut painter);
+            }
 
             if !self.traversal_manager.currently_traversing {
-                if let Some(traversal) = &self.traversal_manager.traversal {
-                    self.painter.paint_path(traversal.end_node, graph, &painter);
+                if let Some(end_node) = self.traversal_manager.path_end() {
+                    self.painter.paint_path(end_node, graph, &mut painter);
                 }
             } else {
                 ctx.request_repaint();
@@ -61,24 +90,66 @@ impl GraphApp {
         }
 
         self.handle_drag(ctx);
+        self.handle_shortcuts(ctx);
 
         if let Some(graph) = self.graphs.get_mut(self.curr_graph) {
             if self.traversal_manager.auto {
                 self.traversal_manager.update(graph);
             }
         }
+    }
 
-        if self.curr_graph != curr_graph || (self.curr_graph == 0 && len != self.graphs.len()) {
-            menus.graph_updated(self);
+    fn handle_drag(&mut self, ctx: &Context) {
+        if let Some(g) = self.graphs.get_mut(self.curr_graph) {
+            Self::handle_drag_internal(g, ctx, self.painter.node_radius, &mut self.curr_drag)
         }
     }
 
-    fn handle_drag(&mut self, ctx: &Context) {
-        match &mut self.graphs.get_mut(self.curr_graph) {
-            Some(g) => {
-                Self::handle_drag_internal(g, ctx, self.painter.node_radius, &mut self.curr_drag)
+    fn handle_shortcuts(&mut self, ctx: &Context) {
+        // Text boxes have their own undo
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+
+        let (undo, redo) = {
+            let input = ctx.input();
+            let pressed = input.modifiers.command && input.key_pressed(Key::Z);
+            (
+                pressed && !input.modifiers.shift,
+                pressed && input.modifiers.shift,
+            )
+        };
+
+        if undo {
+            self.undo();
+        } else if redo {
+            self.redo();
+        }
+    }
+
+    pub fn undo(&mut self) {
+        if let Some(graph) = self.graphs.get_mut(self.curr_graph) {
+            if let Some(structural) = graph.undo() {
+                self.edited_structure(structural);
+            }
+        }
+    }
+
+    pub fn redo(&mut self) {
+        if let Some(graph) = self.graphs.get_mut(self.curr_graph) {
+            if let Some(structural) = graph.redo() {
+                self.edited_structure(structural);
+            }
+        }
+    }
+
+    /// Stops any traversal that could be holding onto nodes or edges that have just gone away
+    fn edited_structure(&mut self, structural: bool) {
+        if structural {
+            self.traversal_manager.stop_traversal();
+            if let Some(graph) = self.graphs.get_mut(self.curr_graph) {
+                graph.reset();
             }
-            None => {}
         }
     }
 
@@ -86,7 +157,7 @@ impl GraphApp {
         graph: &mut Graph,
         ctx: &Context,
         node_radius: f32,
-        curr_drag: &mut Option<NodeIndex>,
+        curr_drag: &mut Option<(NodeIndex, (f32, f32))>,
     ) {
         // This has to be done before we take input because ctx.input() locks all ctx
         let window_size = ctx.available_rect().size();
@@ -98,14 +169,33 @@ impl GraphApp {
         let input = ctx.input();
         let pointer = &input.pointer;
 
-        if let Some(idx) = curr_drag {
+        if let Some((idx, start)) = *curr_drag {
             if pointer.any_released() {
                 *curr_drag = None;
+
+                // The whole drag becomes a single edit
+                if let Some(node) = graph.try_get_node(idx) {
+                    if node.get_pos() != start {
+                        let new = node.get_pos();
+                        graph.edit(
+                            GraphEdit::Move {
+                                idx,
+                                old: start,
+                                new,
+                            },
+                            false,
+                        );
+                    }
+                }
             } else if let Some(pos) = pointer.interact_pos() {
                 let x = pos.x / sf_x;
                 let y = pos.y / sf_y;
 
-                *graph.get_node_mut(*idx).get_pos_mut() = (x, y);
+                match graph.try_get_node_mut(idx) {
+                    Some(node) => *node.get_pos_mut() = (x, y),
+                    // The node was removed while we were dragging it
+                    None => *curr_drag = None,
+                }
             }
         } else if pointer.any_pressed() && !is_over_menu {
             if let Some(pos) = pointer.interact_pos() {
@@ -118,7 +208,7 @@ impl GraphApp {
                     let offset_y = scaled_y - pos.y;
 
                     if offset_x * offset_x + offset_y * offset_y <= node_radius * node_radius {
-                        *curr_drag = Some(node.get_id());
+                        *curr_drag = Some((node.get_id(), node.get_pos()));
                         break;
                     }
                 }
@@ -0,0 +1,155 @@
+//! Reading and writing files in a way that works on both native and the web.
+//!
+//! On native we work with file paths directly.
+//! On the web we can't touch the filesystem so saving offers a download,
+//! and opening asks the browser for an upload which finishes some time later.
+
+#[cfg(target_arch = "wasm32")]
+use std::{cell::RefCell, rc::Rc};
+
+use egui::Context;
+
+/// A file the user has opened
+pub struct LoadedFile {
+    pub name: String,
+    pub contents: String,
+}
+
+/// Saves `contents` to `path`
+///
+/// On the web `path` is used as the name of the downloaded file.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(path: &str, contents: &str) -> Result<(), String> {
+    std::fs::write(path, contents).map_err(|e| format!("Couldn't save {path}: {e}"))
+}
+
+/// Saves `contents` to `path`
+///
+/// On the web `path` is used as the name of the downloaded file.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(path: &str, contents: &str) -> Result<(), String> {
+    use wasm_bindgen::JsCast;
+    use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, Url};
+
+    let to_string = |e: wasm_bindgen::JsValue| format!("Couldn't download {path}: {e:?}");
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| "Couldn't access the page".to_owned())?;
+
+    let parts = js_sys::Array::of1(&contents.into());
+    let blob =
+        Blob::new_with_str_sequence_and_options(&parts, BlobPropertyBag::new().type_("text/plain"))
+            .map_err(to_string)?;
+    let url = Url::create_object_url_with_blob(&blob).map_err(to_string)?;
+
+    let anchor: HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(to_string)?
+        .unchecked_into();
+    anchor.set_href(&url);
+    anchor.set_download(path);
+    anchor.click();
+
+    Url::revoke_object_url(&url).map_err(to_string)
+}
+
+/// Opens files chosen by the user
+///
+/// Call [`FileLoader::open`] to start loading a file
+/// and then poll [`FileLoader::take`] each frame until the file shows up.
+#[derive(Default)]
+pub struct FileLoader {
+    #[cfg(not(target_arch = "wasm32"))]
+    loaded: Option<LoadedFile>,
+    #[cfg(target_arch = "wasm32")]
+    loaded: Rc<RefCell<Option<LoadedFile>>>,
+}
+
+impl FileLoader {
+    /// Reads the file at `path`
+    ///
+    /// `accept` is ignored on native.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open(&mut self, _ctx: &Context, path: &str, _accept: &str) -> Result<(), String> {
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| format!("Couldn't open {path}: {e}"))?;
+
+        self.loaded = Some(LoadedFile {
+            name: path.to_owned(),
+            contents,
+        });
+
+        Ok(())
+    }
+
+    /// Asks the browser to upload a file matching `accept`, eg. `".json"`
+    ///
+    /// `path` is ignored on the web.
+    #[cfg(target_arch = "wasm32")]
+    pub fn open(&mut self, ctx: &Context, _path: &str, accept: &str) -> Result<(), String> {
+        use wasm_bindgen::{closure::Closure, JsCast};
+        use web_sys::{FileReader, HtmlInputElement};
+
+        let to_string = |e: wasm_bindgen::JsValue| format!("Couldn't open a file: {e:?}");
+
+        let document = web_sys::window()
+            .and_then(|w| w.document())
+            .ok_or_else(|| "Couldn't access the page".to_owned())?;
+
+        let input: HtmlInputElement = document
+            .create_element("input")
+            .map_err(to_string)?
+            .unchecked_into();
+        input.set_type("file");
+        input.set_accept(accept);
+
+        let loaded = self.loaded.clone();
+        let ctx = ctx.clone();
+        let picker = input.clone();
+
+        let on_change = Closure::once(move || {
+            let Some(file) = picker.files().and_then(|f| f.get(0)) else {
+                return;
+            };
+            let Ok(reader) = FileReader::new() else {
+                return;
+            };
+
+            let name = file.name();
+            let reader_handle = reader.clone();
+
+            let on_load = Closure::once(move || {
+                if let Some(contents) = reader_handle.result().ok().and_then(|r| r.as_string()) {
+                    *loaded.borrow_mut() = Some(LoadedFile { name, contents });
+                    // The upload finishes outside of egui's event loop so we need to wake it up
+                    ctx.request_repaint();
+                }
+            });
+
+            reader.set_onload(Some(on_load.as_ref().unchecked_ref()));
+            on_load.forget();
+
+            let _ = reader.read_as_text(&file);
+        });
+
+        input.set_onchange(Some(on_change.as_ref().unchecked_ref()));
+        on_change.forget();
+
+        input.click();
+
+        Ok(())
+    }
+
+    /// Takes the most recently opened file if there is one
+    pub fn take(&mut self) -> Option<LoadedFile> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.loaded.take();
+
+        #[cfg(target_arch = "wasm32")]
+        return self.loaded.borrow_mut().take();
+    }
+}
+
+/// Whether files are chosen by typing a path, which is only the case on native
+pub const USES_PATHS: bool = cfg!(not(target_arch = "wasm32"));
@@ -0,0 +1,645 @@
+use std::collections::HashMap;
+
+use crate::{
+    app::INTERNAL_HEIGHT,
+    formats::{hex_color, place_nodes, ParseError},
+    graph::Graph,
+    painter::GraphPainter,
+};
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Id(String),
+    /// Quoted ids can never be keywords so we keep them separate
+    QuotedId(String),
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equals,
+    Semicolon,
+    Comma,
+    Colon,
+    /// `->` if true, `--` if false
+    EdgeOp(bool),
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn new(text: &'a str) -> Self {
+        Lexer {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(c)
+    }
+
+    /// Skips whitespace and comments
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        // `#` lines are only comments at the start of a line
+        let mut line_start = self.column == 1;
+
+        while let Some(&c) = self.chars.peek() {
+            match c {
+                '\n' => {
+                    self.next_char();
+                    line_start = true;
+                }
+                c if c.is_whitespace() => {
+                    self.next_char();
+                }
+                '#' if line_start => self.skip_line(),
+                '/' => {
+                    let mut lookahead = self.chars.clone();
+                    lookahead.next();
+
+                    match lookahead.next() {
+                        Some('/') => self.skip_line(),
+                        Some('*') => {
+                            let (line, column) = (self.line, self.column);
+                            self.next_char();
+                            self.next_char();
+
+                            let mut last = ' ';
+                            loop {
+                                match self.next_char() {
+                                    Some('/') if last == '*' => break,
+                                    Some(c) => last = c,
+                                    None => {
+                                        return Err(ParseError::new(
+                                            line,
+                                            column,
+                                            "Unterminated comment",
+                                        ))
+                                    }
+                                }
+                            }
+                        }
+                        _ => return Ok(()),
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.next_char() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    /// Gets the next token along with the line and column it started at
+    fn next_token(&mut self) -> Result<Option<(Token, usize, usize)>, ParseError> {
+        self.skip_trivia()?;
+
+        let (line, column) = (self.line, self.column);
+        let Some(c) = self.next_char() else {
+            return Ok(None);
+        };
+
+        let token = match c {
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '=' => Token::Equals,
+            ';' => Token::Semicolon,
+            ',' => Token::Comma,
+            ':' => Token::Colon,
+            '-' if matches!(self.chars.peek(), Some('>' | '-')) => {
+                Token::EdgeOp(self.next_char() == Some('>'))
+            }
+            '"' => {
+                let mut id = String::new();
+
+                loop {
+                    match self.next_char() {
+                        Some('\\') => match self.next_char() {
+                            Some(c @ ('"' | '\\')) => id.push(c),
+                            // A backslash followed by a newline continues the string
+                            Some('\n') => {}
+                            Some(c) => {
+                                id.push('\\');
+                                id.push(c);
+                            }
+                            None => break,
+                        },
+                        Some('"') => break,
+                        Some(c) => id.push(c),
+                        None => return Err(ParseError::new(line, column, "Unterminated string")),
+                    }
+                }
+
+                Token::QuotedId(id)
+            }
+            '<' => {
+                // Html strings are kept as is, including any nested tags
+                let mut id = String::new();
+                let mut depth = 1;
+
+                loop {
+                    let Some(c) = self.next_char() else {
+                        return Err(ParseError::new(line, column, "Unterminated html string"));
+                    };
+
+                    match c {
+                        '<' => depth += 1,
+                        '>' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+
+                    id.push(c);
+                }
+
+                Token::QuotedId(id)
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' || c == '-' => {
+                let mut id = String::from(c);
+
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_alphanumeric() || c == '_' || c == '.' {
+                        id.push(c);
+                        self.next_char();
+                    } else {
+                        break;
+                    }
+                }
+
+                Token::Id(id)
+            }
+            c => {
+                return Err(ParseError::new(
+                    line,
+                    column,
+                    format!("Unexpected character '{c}'"),
+                ))
+            }
+        };
+
+        Ok(Some((token, line, column)))
+    }
+}
+
+type Attributes = Vec<(String, String)>;
+
+struct NodeData {
+    id: String,
+    attributes: HashMap<String, String>,
+}
+
+struct EdgeData {
+    from: usize,
+    to: usize,
+    attributes: HashMap<String, String>,
+}
+
+/// Either side of an edge statement, subgraphs connect to every node inside of them
+enum Operand {
+    Node(usize),
+    Subgraph(Vec<usize>),
+}
+
+impl Operand {
+    fn nodes(&self) -> &[usize] {
+        match self {
+            Operand::Node(n) => std::slice::from_ref(n),
+            Operand::Subgraph(nodes) => nodes,
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize, usize)>,
+    pos: usize,
+    end: (usize, usize),
+    directed: bool,
+    nodes: Vec<NodeData>,
+    node_lookup: HashMap<String, usize>,
+    edges: Vec<EdgeData>,
+}
+
+impl Parser {
+    fn new(text: &str) -> Result<Self, ParseError> {
+        let mut lexer = Lexer::new(text);
+        let mut tokens = Vec::new();
+
+        while let Some(token) = lexer.next_token()? {
+            tokens.push(token);
+        }
+
+        Ok(Parser {
+            tokens,
+            pos: 0,
+            end: (lexer.line, lexer.column),
+            directed: false,
+            nodes: Vec::new(),
+            node_lookup: HashMap::new(),
+            edges: Vec::new(),
+        })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(t, ..)| t)
+    }
+
+    fn peek_next(&self) -> Option<&Token> {
+        self.tokens.get(self.pos + 1).map(|(t, ..)| t)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(t, ..)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    /// Makes an error pointing at the current token
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        let (line, column) = self
+            .tokens
+            .get(self.pos)
+            .map_or(self.end, |(_, line, column)| (*line, *column));
+
+        ParseError::new(line, column, message)
+    }
+
+    fn expect(&mut self, token: Token, name: &str) -> Result<(), ParseError> {
+        if self.peek() == Some(&token) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error(format!("Expected '{name}'")))
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Id(id)) if id.eq_ignore_ascii_case(keyword))
+    }
+
+    fn is_id(&self) -> bool {
+        matches!(self.peek(), Some(Token::Id(_) | Token::QuotedId(_)))
+    }
+
+    fn id(&mut self) -> Result<String, ParseError> {
+        match self.peek() {
+            Some(Token::Id(_) | Token::QuotedId(_)) => match self.advance() {
+                Some(Token::Id(id) | Token::QuotedId(id)) => Ok(id),
+                _ => unreachable!(),
+            },
+            _ => Err(self.error("Expected an id")),
+        }
+    }
+
+    fn graph(&mut self) -> Result<Option<String>, ParseError> {
+        if self.is_keyword("strict") {
+            self.advance();
+        }
+
+        if self.is_keyword("graph") {
+            self.directed = false;
+        } else if self.is_keyword("digraph") {
+            self.directed = true;
+        } else {
+            return Err(self.error("Expected 'graph' or 'digraph'"));
+        }
+        self.advance();
+
+        let name = if self.is_id() { Some(self.id()?) } else { None };
+
+        self.expect(Token::LBrace, "{")?;
+        self.stmt_list(HashMap::new(), HashMap::new())?;
+        self.expect(Token::RBrace, "}")?;
+
+        if self.peek().is_some() {
+            return Err(self.error("Unexpected content after the end of the graph"));
+        }
+
+        Ok(name)
+    }
+
+    /// Parses statements until the closing brace, returning every node mentioned
+    ///
+    /// Default attributes set inside of a block only apply to that block so we take them by value.
+    fn stmt_list(
+        &mut self,
+        mut node_defaults: HashMap<String, String>,
+        mut edge_defaults: HashMap<String, String>,
+    ) -> Result<Vec<usize>, ParseError> {
+        let mut members = Vec::new();
+
+        loop {
+            match self.peek() {
+                None => return Err(self.error("Expected '}'")),
+                Some(Token::RBrace) => return Ok(members),
+                Some(Token::Semicolon) => {
+                    self.advance();
+                }
+                _ => self.stmt(&mut node_defaults, &mut edge_defaults, &mut members)?,
+            }
+        }
+    }
+
+    fn stmt(
+        &mut self,
+        node_defaults: &mut HashMap<String, String>,
+        edge_defaults: &mut HashMap<String, String>,
+        members: &mut Vec<usize>,
+    ) -> Result<(), ParseError> {
+        let is_attr_stmt = matches!(self.peek_next(), Some(Token::LBracket))
+            && (self.is_keyword("graph") || self.is_keyword("node") || self.is_keyword("edge"));
+
+        if is_attr_stmt {
+            let keyword = self.id()?.to_lowercase();
+            let attributes = self.attr_list()?;
+
+            match keyword.as_str() {
+                "node" => node_defaults.extend(attributes),
+                "edge" => edge_defaults.extend(attributes),
+                // We don't have anywhere to put graph attributes
+                _ => {}
+            }
+
+            return Ok(());
+        }
+
+        // A lone `a = b` sets a graph attribute, which we ignore
+        if self.is_id() && self.peek_next() == Some(&Token::Equals) {
+            self.advance();
+            self.advance();
+            self.id()?;
+            return Ok(());
+        }
+
+        let first = self.operand(node_defaults, edge_defaults, members)?;
+
+        if !matches!(self.peek(), Some(Token::EdgeOp(_))) {
+            if let Operand::Node(idx) = first {
+                let attributes = self.attr_list()?;
+                self.nodes[idx].attributes.extend(attributes);
+            }
+
+            return Ok(());
+        }
+
+        let mut operands = vec![first];
+
+        while let Some(Token::EdgeOp(directed)) = self.peek() {
+            if *directed != self.directed {
+                return Err(self.error(if self.directed {
+                    "'--' can't be used in a digraph"
+                } else {
+                    "'->' can't be used in an undirected graph"
+                }));
+            }
+
+            self.advance();
+            operands.push(self.operand(node_defaults, edge_defaults, members)?);
+        }
+
+        let mut attributes = edge_defaults.clone();
+        attributes.extend(self.attr_list()?);
+
+        for pair in operands.windows(2) {
+            for &from in pair[0].nodes() {
+                for &to in pair[1].nodes() {
+                    self.edges.push(EdgeData {
+                        from,
+                        to,
+                        attributes: attributes.clone(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn operand(
+        &mut self,
+        node_defaults: &HashMap<String, String>,
+        edge_defaults: &HashMap<String, String>,
+        members: &mut Vec<usize>,
+    ) -> Result<Operand, ParseError> {
+        if self.is_keyword("subgraph") || self.peek() == Some(&Token::LBrace) {
+            if self.is_keyword("subgraph") {
+                self.advance();
+
+                if self.is_id() {
+                    self.id()?;
+                }
+            }
+
+            self.expect(Token::LBrace, "{")?;
+            let nodes = self.stmt_list(node_defaults.clone(), edge_defaults.clone())?;
+            self.expect(Token::RBrace, "}")?;
+
+            members.extend(&nodes);
+            Ok(Operand::Subgraph(nodes))
+        } else {
+            let id = self.id()?;
+
+            // Ports only change where edges attach so we skip them
+            while self.peek() == Some(&Token::Colon) {
+                self.advance();
+                self.id()?;
+            }
+
+            let idx = match self.node_lookup.get(&id) {
+                Some(idx) => *idx,
+                None => {
+                    self.nodes.push(NodeData {
+                        id: id.clone(),
+                        attributes: node_defaults.clone(),
+                    });
+                    self.node_lookup.insert(id, self.nodes.len() - 1);
+                    self.nodes.len() - 1
+                }
+            };
+
+            members.push(idx);
+            Ok(Operand::Node(idx))
+        }
+    }
+
+    /// Parses any number of `[a = b, c = d]` lists
+    fn attr_list(&mut self) -> Result<Attributes, ParseError> {
+        let mut attributes = Vec::new();
+
+        while self.peek() == Some(&Token::LBracket) {
+            self.advance();
+
+            loop {
+                match self.peek() {
+                    Some(Token::RBracket) => {
+                        self.advance();
+                        break;
+                    }
+                    Some(Token::Comma | Token::Semicolon) => {
+                        self.advance();
+                    }
+                    _ => {
+                        let key = self.id()?;
+                        let value = if self.peek() == Some(&Token::Equals) {
+                            self.advance();
+                            self.id()?
+                        } else {
+                            "true".to_owned()
+                        };
+
+                        attributes.push((key, value));
+                    }
+                }
+            }
+        }
+
+        Ok(attributes)
+    }
+}
+
+/// Parses a graph written in the Graphviz DOT language
+///
+/// Node labels become node names, `weight` or numeric `label`s become edge weights,
+/// and `pos` is used to place nodes.
+pub fn from_dot(text: &str) -> Result<Graph, ParseError> {
+    let mut parser = Parser::new(text)?;
+    let name = parser.graph()?;
+
+    let weights = parser
+        .edges
+        .iter()
+        .map(|e| {
+            e.attributes
+                .get("weight")
+                .or_else(|| e.attributes.get("label"))
+                .and_then(|w| w.trim().parse::<f32>().ok())
+        })
+        .collect::<Vec<_>>();
+
+    let mut graph = Graph::new(
+        name.unwrap_or_else(|| "DOT Graph".to_owned()),
+        parser.directed,
+        weights.iter().any(Option::is_some),
+    );
+
+    let positions = place_nodes(
+        &parser
+            .nodes
+            .iter()
+            .map(|n| {
+                let pos = n.attributes.get("pos")?;
+                let (x, y) = pos.trim_end_matches('!').split_once(',')?;
+                // DOT has y going up while we have it going down
+                Some((
+                    x.trim().parse().ok()?,
+                    INTERNAL_HEIGHT - y.trim().parse::<f32>().ok()?,
+                ))
+            })
+            .collect::<Vec<_>>(),
+    );
+
+    let ids = parser
+        .nodes
+        .into_iter()
+        .zip(positions)
+        .map(|(mut node, pos)| {
+            let name = match node.attributes.remove("label") {
+                // `\N` is DOT's way of saying "use the node's id"
+                Some(label) if label != "\\N" => label,
+                _ => node.id,
+            };
+
+            graph.add_node(pos, name, Vec::new())
+        })
+        .collect::<Vec<_>>();
+
+    for (edge, weight) in parser.edges.iter().zip(weights) {
+        graph.add_edge(ids[edge.from], ids[edge.to], weight);
+    }
+
+    Ok(graph)
+}
+
+/// Writes a graph in the Graphviz DOT language
+///
+/// Nodes are filled with the color they're currently painted with, including any group colors,
+/// so any traversal in progress is kept.
+pub fn to_dot(graph: &Graph, painter: &GraphPainter) -> String {
+    let positions = graph
+        .get_nodes()
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.get_id(), i))
+        .collect::<HashMap<_, _>>();
+
+    let (keyword, edge_op) = if graph.is_directed() {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+
+    let mut out = format!("{keyword} {} {{\n", quote(&graph.get_name()));
+
+    for (i, node) in graph.get_nodes().iter().enumerate() {
+        let (x, y) = node.get_pos();
+
+        out.push_str(&format!(
+            "    {i} [label={}, pos=\"{x},{}!\", style=filled, fillcolor=\"{}\", color=\"{}\", \
+             fontcolor=\"{}\"];\n",
+            quote(node.get_name()),
+            INTERNAL_HEIGHT - y,
+            hex_color(painter.node_fill(node)),
+            hex_color(painter.node_color),
+            hex_color(painter.node_text_color),
+        ));
+    }
+
+    for edge in graph.get_edges() {
+        let (weight, a, b) = edge.get_weighted_nodes();
+        let (a, b) = (positions[&a], positions[&b]);
+
+        // Graphviz only takes whole, non-negative weights, anything else just goes in the label
+        if graph.is_weighted() && weight.is_sign_positive() && weight.fract() == 0.0 {
+            out.push_str(&format!(
+                "    {a} {edge_op} {b} [weight={weight}, label=\"{weight}\"];\n"
+            ));
+        } else if graph.is_weighted() {
+            out.push_str(&format!("    {a} {edge_op} {b} [label=\"{weight}\"];\n"));
+        } else {
+            out.push_str(&format!("    {a} {edge_op} {b};\n"));
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
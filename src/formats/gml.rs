@@ -0,0 +1,516 @@
+use std::collections::HashMap;
+
+use crate::{
+    formats::{place_nodes, ParseError},
+    graph::{AttributeValue, Attributes, Graph},
+};
+
+enum Token {
+    Key(String),
+    Value(AttributeValue),
+    LBracket,
+    RBracket,
+}
+
+/// A single `key value` pair along with where it started
+struct Item {
+    key: String,
+    value: Value,
+    line: usize,
+    column: usize,
+}
+
+enum Value {
+    Scalar(AttributeValue),
+    List(Vec<Item>),
+}
+
+impl Value {
+    fn into_attribute(self) -> AttributeValue {
+        match self {
+            Value::Scalar(v) => v,
+            Value::List(items) => AttributeValue::List(
+                items
+                    .into_iter()
+                    .map(|i| (i.key, i.value.into_attribute()))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn as_number(&self) -> Option<f64> {
+        match self {
+            Value::Scalar(AttributeValue::Int(i)) => Some(*i as f64),
+            Value::Scalar(AttributeValue::Float(f)) => Some(*f),
+            _ => None,
+        }
+    }
+}
+
+struct Lexer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+
+        Some(c)
+    }
+
+    fn next_token(&mut self) -> Result<Option<(Token, usize, usize)>, ParseError> {
+        // Skip whitespace and comments
+        while let Some(&c) = self.chars.peek() {
+            if c == '#' {
+                while !matches!(self.next_char(), Some('\n') | None) {}
+            } else if c.is_whitespace() {
+                self.next_char();
+            } else {
+                break;
+            }
+        }
+
+        let (line, column) = (self.line, self.column);
+        let Some(c) = self.next_char() else {
+            return Ok(None);
+        };
+
+        let token = match c {
+            '[' => Token::LBracket,
+            ']' => Token::RBracket,
+            '"' => {
+                let mut text = String::new();
+
+                loop {
+                    match self.next_char() {
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                        None => return Err(ParseError::new(line, column, "Unterminated string")),
+                    }
+                }
+
+                Token::Value(AttributeValue::String(decode_entities(&text)))
+            }
+            c if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => {
+                let mut number = String::from(c);
+
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.') {
+                        number.push(c);
+                        self.next_char();
+                    } else {
+                        break;
+                    }
+                }
+
+                if let Ok(i) = number.parse() {
+                    Token::Value(AttributeValue::Int(i))
+                } else if let Ok(f) = number.parse() {
+                    Token::Value(AttributeValue::Float(f))
+                } else {
+                    return Err(ParseError::new(
+                        line,
+                        column,
+                        format!("Invalid number '{number}'"),
+                    ));
+                }
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut key = String::from(c);
+
+                while let Some(&c) = self.chars.peek() {
+                    if c.is_ascii_alphanumeric() || c == '_' {
+                        key.push(c);
+                        self.next_char();
+                    } else {
+                        break;
+                    }
+                }
+
+                Token::Key(key)
+            }
+            c => {
+                return Err(ParseError::new(
+                    line,
+                    column,
+                    format!("Unexpected character '{c}'"),
+                ))
+            }
+        };
+
+        Ok(Some((token, line, column)))
+    }
+
+    /// Reads `key value` pairs until the end of the list or file
+    fn list(&mut self, in_brackets: bool) -> Result<Vec<Item>, ParseError> {
+        let mut items = Vec::new();
+
+        loop {
+            let (key, line, column) = match self.next_token()? {
+                Some((Token::Key(key), line, column)) => (key, line, column),
+                Some((Token::RBracket, ..)) if in_brackets => return Ok(items),
+                None if !in_brackets => return Ok(items),
+                None => return Err(ParseError::new(self.line, self.column, "Expected ']'")),
+                Some((_, line, column)) => {
+                    return Err(ParseError::new(line, column, "Expected a key"))
+                }
+            };
+
+            let value = match self.next_token()? {
+                Some((Token::Value(v), ..)) => Value::Scalar(v),
+                Some((Token::LBracket, ..)) => Value::List(self.list(true)?),
+                Some((_, line, column)) => {
+                    return Err(ParseError::new(
+                        line,
+                        column,
+                        format!("Expected a value for '{key}'"),
+                    ))
+                }
+                None => {
+                    return Err(ParseError::new(
+                        self.line,
+                        self.column,
+                        format!("Expected a value for '{key}'"),
+                    ))
+                }
+            };
+
+            items.push(Item {
+                key,
+                value,
+                line,
+                column,
+            });
+        }
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "quot" => '"',
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "apos" => '\'',
+                code => code
+                    .strip_prefix('#')
+                    .and_then(|c| c.parse().ok())
+                    .and_then(char::from_u32)?,
+            };
+
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn encode_entities(text: &str) -> String {
+    text.replace('&', "&amp;").replace('"', "&quot;")
+}
+
+/// Node ids can be numbers or strings, we compare them as text
+fn id_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Scalar(AttributeValue::Int(i)) => Some(i.to_string()),
+        Value::Scalar(AttributeValue::String(s)) => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Parses a graph in the Graph Modelling Language
+///
+/// Node positions come from `graphics [ x y ]` and edge weights from `weight`,
+/// everything else is kept as attributes.
+pub fn from_gml(text: &str) -> Result<Graph, ParseError> {
+    let mut lexer = Lexer {
+        chars: text.chars().peekable(),
+        line: 1,
+        column: 1,
+    };
+
+    let items = lexer.list(false)?;
+
+    let Some(graph_items) = items.into_iter().find_map(|i| match i.value {
+        Value::List(items) if i.key == "graph" => Some(items),
+        _ => None,
+    }) else {
+        return Err(ParseError::new(lexer.line, lexer.column, "No graph found"));
+    };
+
+    let mut name = "GML Graph".to_owned();
+    let mut directed = false;
+    let mut graph_attributes = Attributes::new();
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+
+    for item in graph_items {
+        match (item.key.as_str(), item.value) {
+            ("directed", value) => directed = value.as_number() == Some(1.0),
+            ("label", Value::Scalar(AttributeValue::String(label))) => name = label,
+            ("node", Value::List(items)) => nodes.push((items, item.line, item.column)),
+            ("edge", Value::List(items)) => edges.push((items, item.line, item.column)),
+            (_, value) => {
+                graph_attributes.insert(item.key, value.into_attribute());
+            }
+        }
+    }
+
+    let mut node_data = Vec::new();
+    let mut ids = HashMap::new();
+
+    for (items, line, column) in nodes {
+        let mut id = None;
+        let mut label = None;
+        let mut pos = None;
+        let mut attributes = Attributes::new();
+
+        for item in items {
+            match (item.key.as_str(), item.value) {
+                ("id", value) => id = id_text(&value),
+                ("label", Value::Scalar(value)) => label = Some(value.to_string()),
+                ("graphics", Value::List(graphics)) => {
+                    let coordinate = |key| {
+                        graphics
+                            .iter()
+                            .find(|g| g.key == key)
+                            .and_then(|g| g.value.as_number())
+                    };
+                    pos = coordinate("x").zip(coordinate("y"));
+
+                    // A lone coordinate can't place the node so it's kept with the other graphics
+                    let rest = graphics
+                        .into_iter()
+                        .filter(|g| pos.is_none() || (g.key != "x" && g.key != "y"))
+                        .map(|g| (g.key, g.value.into_attribute()))
+                        .collect::<Vec<_>>();
+
+                    if !rest.is_empty() {
+                        attributes.insert("graphics".to_owned(), AttributeValue::List(rest));
+                    }
+                }
+                (_, value) => {
+                    attributes.insert(item.key, value.into_attribute());
+                }
+            }
+        }
+
+        let Some(id) = id else {
+            return Err(ParseError::new(line, column, "Node is missing an id"));
+        };
+
+        if ids.insert(id.clone(), node_data.len()).is_some() {
+            return Err(ParseError::new(
+                line,
+                column,
+                format!("Node id {id} is used more than once"),
+            ));
+        }
+
+        let pos = pos.map(|(x, y)| (x as f32, y as f32));
+
+        node_data.push((label.unwrap_or(id), pos, attributes));
+    }
+
+    let mut edge_data = Vec::new();
+
+    for (items, line, column) in edges {
+        let mut source = None;
+        let mut target = None;
+        let mut weight = None;
+        let mut attributes = Attributes::new();
+
+        for item in items {
+            match (item.key.as_str(), &item.value) {
+                ("source", value) => source = Some((id_text(value), item.line, item.column)),
+                ("target", value) => target = Some((id_text(value), item.line, item.column)),
+                ("weight", value) if value.as_number().is_some() => {
+                    weight = value.as_number().map(|w| w as f32)
+                }
+                _ => {
+                    attributes.insert(item.key, item.value.into_attribute());
+                }
+            }
+        }
+
+        let lookup = |end: Option<(Option<String>, usize, usize)>, name: &str| match end {
+            Some((Some(id), line, column)) => ids
+                .get(&id)
+                .copied()
+                .ok_or_else(|| ParseError::new(line, column, format!("Unknown node {id}"))),
+            Some((None, line, column)) => {
+                Err(ParseError::new(line, column, format!("Invalid {name}")))
+            }
+            None => Err(ParseError::new(
+                line,
+                column,
+                format!("Edge is missing a {name}"),
+            )),
+        };
+
+        edge_data.push((
+            lookup(source, "source")?,
+            lookup(target, "target")?,
+            weight,
+            attributes,
+        ));
+    }
+
+    let mut graph = Graph::new(
+        name,
+        directed,
+        edge_data.iter().any(|(_, _, w, _)| w.is_some()),
+    );
+    *graph.attributes_mut() = graph_attributes;
+
+    let positions = place_nodes(&node_data.iter().map(|(_, p, _)| *p).collect::<Vec<_>>());
+
+    let node_ids = node_data
+        .into_iter()
+        .zip(positions)
+        .map(|((name, _, attributes), pos)| {
+            let idx = graph.add_node(pos, name, Vec::new());
+            *graph.get_node_mut(idx).attributes_mut() = attributes;
+            idx
+        })
+        .collect::<Vec<_>>();
+
+    for (a, b, weight, attributes) in edge_data {
+        let (a, b) = (node_ids[a], node_ids[b]);
+        graph.add_edge_with_attributes(a, b, weight, attributes);
+    }
+
+    Ok(graph)
+}
+
+/// Writes a graph in the Graph Modelling Language
+pub fn to_gml(graph: &Graph) -> String {
+    let positions = graph
+        .get_nodes()
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.get_id(), i))
+        .collect::<HashMap<_, _>>();
+
+    let mut out = String::from("graph [\n");
+    out.push_str(&format!("  directed {}\n", graph.is_directed() as u8));
+    out.push_str(&format!(
+        "  label \"{}\"\n",
+        encode_entities(&graph.get_name())
+    ));
+    for (key, value) in graph.attributes() {
+        write_attribute(&mut out, key, value, 1);
+    }
+
+    for (i, node) in graph.get_nodes().iter().enumerate() {
+        let (x, y) = node.get_pos();
+
+        out.push_str("  node [\n");
+        out.push_str(&format!("    id {i}\n"));
+        out.push_str(&format!(
+            "    label \"{}\"\n",
+            encode_entities(node.get_name())
+        ));
+
+        // Any graphics we didn't understand go back in with the position
+        let mut graphics = vec![
+            ("x".to_owned(), AttributeValue::Float(widen(x))),
+            ("y".to_owned(), AttributeValue::Float(widen(y))),
+        ];
+        if let Some(AttributeValue::List(rest)) = node.attributes().get("graphics") {
+            // A lone coordinate that was kept gets replaced by the node's position
+            graphics.extend(
+                rest.iter()
+                    .filter(|(key, _)| key != "x" && key != "y")
+                    .cloned(),
+            );
+        }
+        write_attribute(&mut out, "graphics", &AttributeValue::List(graphics), 2);
+
+        for (key, value) in node.attributes() {
+            // Graphics that came from another format won't be a list so we can't merge them
+            if key != "graphics" || !matches!(value, AttributeValue::List(_)) {
+                write_attribute(&mut out, key, value, 2);
+            }
+        }
+        out.push_str("  ]\n");
+    }
+
+    for edge in graph.get_edges() {
+        let (weight, a, b) = edge.get_weighted_nodes();
+
+        out.push_str("  edge [\n");
+        out.push_str(&format!("    source {}\n", positions[&a]));
+        out.push_str(&format!("    target {}\n", positions[&b]));
+
+        if graph.is_weighted() {
+            out.push_str(&format!("    weight {weight:?}\n"));
+        }
+
+        if let Some(attributes) = graph.edge_attributes(&edge) {
+            for (key, value) in attributes {
+                write_attribute(&mut out, key, value, 2);
+            }
+        }
+        out.push_str("  ]\n");
+    }
+
+    out.push_str("]\n");
+    out
+}
+
+/// Turns an `f32` into the `f64` that's written the same way, rather than one that shows all the
+/// rounding error of the `f32`
+fn widen(x: f32) -> f64 {
+    x.to_string().parse().unwrap_or(x as f64)
+}
+
+fn write_attribute(out: &mut String, key: &str, value: &AttributeValue, depth: usize) {
+    let indent = "  ".repeat(depth);
+
+    match value {
+        AttributeValue::List(items) => {
+            out.push_str(&format!("{indent}{key} [\n"));
+            for (key, value) in items {
+                write_attribute(out, key, value, depth + 1);
+            }
+            out.push_str(&format!("{indent}]\n"));
+        }
+        AttributeValue::String(s) => {
+            out.push_str(&format!("{indent}{key} \"{}\"\n", encode_entities(s)))
+        }
+        AttributeValue::Float(f) => out.push_str(&format!("{indent}{key} {f:?}\n")),
+        AttributeValue::Int(i) => out.push_str(&format!("{indent}{key} {i}\n")),
+        // GML doesn't have booleans so we use 0 and 1 like `directed` does
+        AttributeValue::Bool(b) => out.push_str(&format!("{indent}{key} {}\n", *b as u8)),
+    }
+}
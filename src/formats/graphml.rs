@@ -0,0 +1,386 @@
+use std::collections::{BTreeMap, HashMap};
+
+use roxmltree::{Document, Node as XmlNode};
+
+use crate::{
+    formats::{place_nodes, ParseError},
+    graph::{AttributeValue, Attributes, Graph},
+};
+
+/// A `<key>` declaration, which says what a `<data>` element holds
+struct Key {
+    domain: String,
+    name: String,
+    kind: String,
+    default: Option<String>,
+}
+
+impl Key {
+    fn applies_to(&self, domain: &str) -> bool {
+        self.domain == domain || self.domain == "all"
+    }
+}
+
+fn error_at(doc: &Document, node: XmlNode, message: impl Into<String>) -> ParseError {
+    let pos = doc.text_pos_at(node.range().start);
+    ParseError::new(pos.row as usize, pos.col as usize, message)
+}
+
+fn children<'a, 'input>(
+    node: XmlNode<'a, 'input>,
+    name: &'static str,
+) -> impl Iterator<Item = XmlNode<'a, 'input>> {
+    node.children()
+        .filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn parse_value(kind: &str, text: &str) -> Option<AttributeValue> {
+    let text = text.trim();
+
+    Some(match kind {
+        "boolean" => AttributeValue::Bool(match text {
+            "true" | "1" => true,
+            "false" | "0" => false,
+            _ => return None,
+        }),
+        "int" | "long" => AttributeValue::Int(text.parse().ok()?),
+        "float" | "double" => AttributeValue::Float(text.parse().ok()?),
+        _ => AttributeValue::String(text.to_owned()),
+    })
+}
+
+/// Reads the `<data>` children of an element, filling in defaults for any that are missing
+fn read_data(
+    doc: &Document,
+    element: XmlNode,
+    domain: &str,
+    keys: &HashMap<String, Key>,
+) -> Result<Attributes, ParseError> {
+    let mut attributes = Attributes::new();
+
+    for data in children(element, "data") {
+        let Some(key) = data.attribute("key").and_then(|k| keys.get(k)) else {
+            return Err(error_at(doc, data, "Data refers to an undeclared key"));
+        };
+
+        let text = data.text().unwrap_or_default();
+        let Some(value) = parse_value(&key.kind, text) else {
+            return Err(error_at(
+                doc,
+                data,
+                format!("Invalid {} value '{text}' for '{}'", key.kind, key.name),
+            ));
+        };
+
+        attributes.insert(key.name.clone(), value);
+    }
+
+    for key in keys.values().filter(|k| k.applies_to(domain)) {
+        if let Some(default) = &key.default {
+            if !attributes.contains_key(&key.name) {
+                if let Some(value) = parse_value(&key.kind, default) {
+                    attributes.insert(key.name.clone(), value);
+                }
+            }
+        }
+    }
+
+    Ok(attributes)
+}
+
+fn number(attributes: &Attributes, name: &str) -> Option<f32> {
+    match attributes.get(name)? {
+        AttributeValue::Int(i) => Some(*i as f32),
+        AttributeValue::Float(f) => Some(*f as f32),
+        _ => None,
+    }
+}
+
+fn take_number(attributes: &mut Attributes, name: &str) -> Option<f32> {
+    let number = number(attributes, name)?;
+    attributes.remove(name);
+    Some(number)
+}
+
+/// Parses a graph in the GraphML format
+///
+/// Node positions come from the `x` and `y` keys, names from `label`, and weights from `weight`.
+/// Any other data is kept as attributes.
+pub fn from_graphml(text: &str) -> Result<Graph, ParseError> {
+    let doc = Document::parse(text).map_err(|e| {
+        let pos = e.pos();
+        // roxmltree puts the position at the end of its messages but we add our own
+        let message = e.to_string();
+        let message = message.trim_end_matches(&format!(" at {pos}"));
+        ParseError::new(pos.row as usize, pos.col as usize, message)
+    })?;
+
+    let root = doc.root_element();
+
+    let keys = children(root, "key")
+        .filter_map(|k| {
+            let id = k.attribute("id")?;
+
+            Some((
+                id.to_owned(),
+                Key {
+                    domain: k.attribute("for").unwrap_or("all").to_owned(),
+                    name: k.attribute("attr.name").unwrap_or(id).to_owned(),
+                    kind: k.attribute("attr.type").unwrap_or("string").to_owned(),
+                    default: children(k, "default")
+                        .next()
+                        .and_then(|d| d.text())
+                        .map(str::to_owned),
+                },
+            ))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let Some(graph_element) = children(root, "graph").next() else {
+        return Err(error_at(&doc, root, "No graph found"));
+    };
+
+    let mut node_data = Vec::new();
+    let mut ids = HashMap::new();
+
+    for node in children(graph_element, "node") {
+        let Some(id) = node.attribute("id") else {
+            return Err(error_at(&doc, node, "Node is missing an id"));
+        };
+
+        if ids.insert(id, node_data.len()).is_some() {
+            return Err(error_at(
+                &doc,
+                node,
+                format!("Node id '{id}' is used more than once"),
+            ));
+        }
+
+        let mut attributes = read_data(&doc, node, "node", &keys)?;
+
+        // A lone coordinate can't place the node so it's kept like any other attribute
+        let pos = number(&attributes, "x").zip(number(&attributes, "y"));
+        if pos.is_some() {
+            attributes.remove("x");
+            attributes.remove("y");
+        }
+
+        let name = match attributes.remove("label") {
+            Some(label) => label.to_string(),
+            None => id.to_owned(),
+        };
+
+        node_data.push((name, pos, attributes));
+    }
+
+    let mut edge_data = Vec::new();
+
+    for edge in children(graph_element, "edge") {
+        let lookup = |end: &str| {
+            let Some(id) = edge.attribute(end) else {
+                return Err(error_at(&doc, edge, format!("Edge is missing a {end}")));
+            };
+
+            ids.get(id)
+                .copied()
+                .ok_or_else(|| error_at(&doc, edge, format!("Unknown node '{id}'")))
+        };
+
+        let (a, b) = (lookup("source")?, lookup("target")?);
+
+        let mut attributes = read_data(&doc, edge, "edge", &keys)?;
+        let weight = take_number(&mut attributes, "weight");
+
+        edge_data.push((a, b, weight, attributes));
+    }
+
+    let mut graph = Graph::new(
+        graph_element
+            .attribute("id")
+            .unwrap_or("GraphML Graph")
+            .to_owned(),
+        graph_element.attribute("edgedefault") == Some("directed"),
+        edge_data.iter().any(|(_, _, w, _)| w.is_some()),
+    );
+    *graph.attributes_mut() = read_data(&doc, graph_element, "graph", &keys)?;
+
+    let positions = place_nodes(&node_data.iter().map(|(_, p, _)| *p).collect::<Vec<_>>());
+
+    let node_ids = node_data
+        .into_iter()
+        .zip(positions)
+        .map(|((name, _, attributes), pos)| {
+            let idx = graph.add_node(pos, name, Vec::new());
+            *graph.get_node_mut(idx).attributes_mut() = attributes;
+            idx
+        })
+        .collect::<Vec<_>>();
+
+    for (a, b, weight, attributes) in edge_data {
+        let (a, b) = (node_ids[a], node_ids[b]);
+        graph.add_edge_with_attributes(a, b, weight, attributes);
+    }
+
+    Ok(graph)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Picks the narrowest GraphML type that can hold every value
+fn key_type<'a>(values: impl Iterator<Item = &'a AttributeValue>) -> &'static str {
+    let mut kind = None;
+
+    for value in values {
+        kind = Some(match (kind, value) {
+            (None | Some("boolean"), AttributeValue::Bool(_)) => "boolean",
+            (None | Some("long"), AttributeValue::Int(_)) => "long",
+            (None | Some("long" | "double"), AttributeValue::Int(_) | AttributeValue::Float(_)) => {
+                "double"
+            }
+            _ => return "string",
+        });
+    }
+
+    kind.unwrap_or("string")
+}
+
+/// Writes a graph in the GraphML format
+pub fn to_graphml(graph: &Graph) -> String {
+    let positions = graph
+        .get_nodes()
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.get_id(), i))
+        .collect::<HashMap<_, _>>();
+
+    let edges = graph.get_edges();
+
+    // Work out which keys we need to declare for the attributes we've kept
+    let mut domains: [(&str, BTreeMap<&String, Vec<&AttributeValue>>); 3] = [
+        ("graph", BTreeMap::new()),
+        ("node", BTreeMap::new()),
+        ("edge", BTreeMap::new()),
+    ];
+
+    let edge_attributes = edges
+        .iter()
+        .filter_map(|e| graph.edge_attributes(e))
+        .collect::<Vec<_>>();
+
+    let attribute_sets = [
+        vec![graph.attributes()],
+        graph.get_nodes().iter().map(|n| n.attributes()).collect(),
+        edge_attributes,
+    ];
+
+    for ((domain, keys), sets) in domains.iter_mut().zip(attribute_sets) {
+        for (name, value) in sets.into_iter().flatten() {
+            // A lone coordinate that was kept gets replaced by the node's position
+            if *domain == "node" && (name == "x" || name == "y") {
+                continue;
+            }
+
+            keys.entry(name).or_default().push(value);
+        }
+    }
+
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<graphml \
+         xmlns=\"http://graphml.graphdrawing.org/xmlns\" \
+         xmlns:xsi=\"http://www.w3.org/2001/XMLSchema-instance\" \
+         xsi:schemaLocation=\"http://graphml.graphdrawing.org/xmlns \
+         http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd\">\n",
+    );
+
+    out.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+    out.push_str("  <key id=\"x\" for=\"node\" attr.name=\"x\" attr.type=\"float\"/>\n");
+    out.push_str("  <key id=\"y\" for=\"node\" attr.name=\"y\" attr.type=\"float\"/>\n");
+    if graph.is_weighted() {
+        out.push_str(
+            "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>\n",
+        );
+    }
+
+    // Maps each domain's attribute names to the id of their key
+    let mut key_ids: [HashMap<&String, String>; 3] = Default::default();
+
+    for ((domain, keys), ids) in domains.iter().zip(&mut key_ids) {
+        for (i, (name, values)) in keys.iter().enumerate() {
+            let id = format!("{}{i}", &domain[..1]);
+
+            out.push_str(&format!(
+                "  <key id=\"{id}\" for=\"{domain}\" attr.name=\"{}\" attr.type=\"{}\"/>\n",
+                escape(name),
+                key_type(values.iter().copied())
+            ));
+
+            ids.insert(*name, id);
+        }
+    }
+
+    let write_data =
+        |out: &mut String, attributes: Option<&Attributes>, ids: &HashMap<_, _>, indent: &str| {
+            for (name, value) in attributes.into_iter().flatten() {
+                let Some(id) = ids.get(name) else {
+                    continue;
+                };
+
+                out.push_str(&format!(
+                    "{indent}<data key=\"{id}\">{}</data>\n",
+                    escape(&value.to_string())
+                ));
+            }
+        };
+
+    out.push_str(&format!(
+        "  <graph id=\"{}\" edgedefault=\"{}\">\n",
+        escape(&graph.get_name()),
+        if graph.is_directed() {
+            "directed"
+        } else {
+            "undirected"
+        }
+    ));
+    write_data(&mut out, Some(graph.attributes()), &key_ids[0], "    ");
+
+    for (i, node) in graph.get_nodes().iter().enumerate() {
+        let (x, y) = node.get_pos();
+
+        out.push_str(&format!("    <node id=\"n{i}\">\n"));
+        out.push_str(&format!(
+            "      <data key=\"label\">{}</data>\n",
+            escape(node.get_name())
+        ));
+        out.push_str(&format!("      <data key=\"x\">{x}</data>\n"));
+        out.push_str(&format!("      <data key=\"y\">{y}</data>\n"));
+        write_data(&mut out, Some(node.attributes()), &key_ids[1], "      ");
+        out.push_str("    </node>\n");
+    }
+
+    for edge in edges {
+        let (weight, a, b) = edge.get_weighted_nodes();
+
+        out.push_str(&format!(
+            "    <edge source=\"n{}\" target=\"n{}\">\n",
+            positions[&a], positions[&b]
+        ));
+        if graph.is_weighted() {
+            out.push_str(&format!("      <data key=\"weight\">{weight}</data>\n"));
+        }
+        write_data(
+            &mut out,
+            graph.edge_attributes(&edge),
+            &key_ids[2],
+            "      ",
+        );
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
@@ -0,0 +1,167 @@
+use std::{collections::HashMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::{Attributes, Graph};
+
+/// The version of the format we write, bump this whenever the format changes in a way older
+/// versions can't read
+///
+/// New optional fields like `attributes` and `seed` don't need a bump, since older versions
+/// skip fields they don't know and newer ones fill in missing fields with defaults.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct GraphFile {
+    version: u32,
+    name: String,
+    directed: bool,
+    weighted: bool,
+    nodes: Vec<NodeData>,
+    edges: Vec<EdgeData>,
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    attributes: Attributes,
+    /// The seed the graph was randomly generated from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct NodeData {
+    id: usize,
+    name: String,
+    pos: (f32, f32),
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    attributes: Attributes,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EdgeData {
+    from: usize,
+    to: usize,
+    weight: f32,
+    #[serde(default, skip_serializing_if = "Attributes::is_empty")]
+    attributes: Attributes,
+}
+
+/// Only used to check the version before we try to parse the rest of the file
+#[derive(Deserialize)]
+struct VersionCheck {
+    version: u32,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    /// The file isn't valid json or doesn't match our schema
+    Schema(serde_json::Error),
+    /// The file was written by a different version of the format
+    Version(u32),
+    /// Two nodes were given the same id
+    DuplicateNode(usize),
+    /// An edge refers to a node id that isn't in the file
+    DanglingNode { edge: usize, node: usize },
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Schema(e) => write!(f, "Invalid graph file: {e}"),
+            LoadError::Version(v) => write!(
+                f,
+                "Graph file is version {v} but only version {FORMAT_VERSION} is supported"
+            ),
+            LoadError::DuplicateNode(id) => write!(f, "Node id {id} is used more than once"),
+            LoadError::DanglingNode { edge, node } => {
+                write!(f, "Edge {edge} refers to node {node} which doesn't exist")
+            }
+        }
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(e: serde_json::Error) -> Self {
+        LoadError::Schema(e)
+    }
+}
+
+pub fn to_json(graph: &Graph) -> String {
+    let positions = graph
+        .get_nodes()
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.get_id(), i))
+        .collect::<HashMap<_, _>>();
+
+    let file = GraphFile {
+        version: FORMAT_VERSION,
+        name: graph.get_name(),
+        directed: graph.is_directed(),
+        weighted: graph.is_weighted(),
+        nodes: graph
+            .get_nodes()
+            .iter()
+            .enumerate()
+            .map(|(i, n)| NodeData {
+                id: i,
+                name: n.get_name().to_owned(),
+                pos: n.get_pos(),
+                attributes: n.attributes().clone(),
+            })
+            .collect(),
+        edges: graph
+            .get_edges()
+            .iter()
+            .map(|e| {
+                let (weight, a, b) = e.get_weighted_nodes();
+                EdgeData {
+                    from: positions[&a],
+                    to: positions[&b],
+                    weight,
+                    attributes: graph.edge_attributes(e).cloned().unwrap_or_default(),
+                }
+            })
+            .collect(),
+        attributes: graph.attributes().clone(),
+        seed: graph.get_seed(),
+    };
+
+    // Our types can always be represented as json so this can't fail
+    serde_json::to_string_pretty(&file).unwrap()
+}
+
+pub fn from_json(text: &str) -> Result<Graph, LoadError> {
+    let VersionCheck { version } = serde_json::from_str(text)?;
+
+    if version != FORMAT_VERSION {
+        return Err(LoadError::Version(version));
+    }
+
+    let file: GraphFile = serde_json::from_str(text)?;
+
+    let mut graph = Graph::new(file.name, file.directed, file.weighted);
+    *graph.attributes_mut() = file.attributes;
+    graph.set_seed(file.seed);
+    let mut ids = HashMap::new();
+
+    for node in file.nodes {
+        let idx = graph.add_node(node.pos, node.name, Vec::new());
+        *graph.get_node_mut(idx).attributes_mut() = node.attributes;
+
+        if ids.insert(node.id, idx).is_some() {
+            return Err(LoadError::DuplicateNode(node.id));
+        }
+    }
+
+    for (i, edge) in file.edges.into_iter().enumerate() {
+        let lookup = |node| {
+            ids.get(&node)
+                .copied()
+                .ok_or(LoadError::DanglingNode { edge: i, node })
+        };
+
+        let (a, b) = (lookup(edge.from)?, lookup(edge.to)?);
+        graph.add_edge_with_attributes(a, b, Some(edge.weight), edge.attributes);
+    }
+
+    Ok(graph)
+}
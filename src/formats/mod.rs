@@ -0,0 +1,178 @@
+pub mod dot;
+pub mod gml;
+pub mod graphml;
+pub mod json;
+pub mod svg;
+
+use std::fmt::Display;
+
+use egui::Color32;
+
+use crate::{
+    app::{INTERNAL_HEIGHT, INTERNAL_WIDTH},
+    graph::{Graph, NodeIndex},
+    painter::GraphPainter,
+};
+
+/// An error in a text based format, pointing at where in the text it happened
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.message, self.line, self.column
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Json,
+    Dot,
+    GraphMl,
+    Gml,
+    Svg,
+}
+
+impl FileFormat {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            FileFormat::Json => "JSON",
+            FileFormat::Dot => "Graphviz DOT",
+            FileFormat::GraphMl => "GraphML",
+            FileFormat::Gml => "GML",
+            FileFormat::Svg => "SVG Image",
+        }
+    }
+
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            FileFormat::Json => "json",
+            FileFormat::Dot => "dot",
+            FileFormat::GraphMl => "graphml",
+            FileFormat::Gml => "gml",
+            FileFormat::Svg => "svg",
+        }
+    }
+
+    pub const fn values() -> [FileFormat; 5] {
+        [
+            FileFormat::Json,
+            FileFormat::Dot,
+            FileFormat::GraphMl,
+            FileFormat::Gml,
+            FileFormat::Svg,
+        ]
+    }
+
+    /// Whether graphs can be read back out of this format, images are export only
+    pub const fn can_load(&self) -> bool {
+        !matches!(self, FileFormat::Svg)
+    }
+
+    /// Writes `graph` in this format
+    ///
+    /// `path_end` is the end of the currently shown path, which only matters for images.
+    pub fn save(
+        &self,
+        graph: &Graph,
+        painter: &GraphPainter,
+        path_end: Option<NodeIndex>,
+    ) -> String {
+        match self {
+            FileFormat::Json => json::to_json(graph),
+            FileFormat::Dot => dot::to_dot(graph, painter),
+            FileFormat::GraphMl => graphml::to_graphml(graph),
+            FileFormat::Gml => gml::to_gml(graph),
+            FileFormat::Svg => svg::to_svg(graph, painter, path_end),
+        }
+    }
+
+    pub fn load(&self, text: &str) -> Result<Graph, String> {
+        match self {
+            FileFormat::Json => json::from_json(text).map_err(|e| e.to_string()),
+            FileFormat::Dot => dot::from_dot(text).map_err(|e| e.to_string()),
+            FileFormat::GraphMl => graphml::from_graphml(text).map_err(|e| e.to_string()),
+            FileFormat::Gml => gml::from_gml(text).map_err(|e| e.to_string()),
+            FileFormat::Svg => Err("SVG images can't be opened as graphs".to_owned()),
+        }
+    }
+}
+
+/// Works out where to put imported nodes
+///
+/// If every position fits on the canvas they're kept as is, otherwise they get scaled to fit.
+/// Nodes without a position are spread around a circle.
+pub fn place_nodes(positions: &[Option<(f32, f32)>]) -> Vec<(f32, f32)> {
+    const MARGIN: f32 = 50.0;
+
+    let known = positions.iter().flatten();
+    let (min_x, min_y, max_x, max_y) = known.fold(
+        (
+            f32::INFINITY,
+            f32::INFINITY,
+            f32::NEG_INFINITY,
+            f32::NEG_INFINITY,
+        ),
+        |(min_x, min_y, max_x, max_y), (x, y)| {
+            (min_x.min(*x), min_y.min(*y), max_x.max(*x), max_y.max(*y))
+        },
+    );
+
+    let fits = min_x >= 0.0 && min_y >= 0.0 && max_x <= INTERNAL_WIDTH && max_y <= INTERNAL_HEIGHT;
+
+    // Keep the aspect ratio so layouts don't get squashed
+    let scale = f32::min(
+        (INTERNAL_WIDTH - 2.0 * MARGIN) / (max_x - min_x),
+        (INTERNAL_HEIGHT - 2.0 * MARGIN) / (max_y - min_y),
+    );
+    let scale = if scale.is_finite() { scale } else { 1.0 };
+
+    let unplaced = positions.iter().filter(|p| p.is_none()).count();
+    let mut placed_on_circle = 0;
+
+    positions
+        .iter()
+        .map(|pos| match pos {
+            Some((x, y)) if fits => (*x, *y),
+            Some((x, y)) => (MARGIN + (x - min_x) * scale, MARGIN + (y - min_y) * scale),
+            None => {
+                let angle = std::f32::consts::TAU * placed_on_circle as f32 / unplaced as f32;
+                placed_on_circle += 1;
+
+                (
+                    INTERNAL_WIDTH / 2.0 + (INTERNAL_WIDTH / 2.0 - 2.0 * MARGIN) * angle.cos(),
+                    INTERNAL_HEIGHT / 2.0 + (INTERNAL_HEIGHT / 2.0 - 2.0 * MARGIN) * angle.sin(),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Formats a color as `#rrggbb`, or `#rrggbbaa` if it's transparent
+pub fn hex_color(color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+
+    if a == u8::MAX {
+        format!("#{r:02x}{g:02x}{b:02x}")
+    } else {
+        format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+    }
+}
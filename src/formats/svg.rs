@@ -0,0 +1,150 @@
+use std::fmt::Write;
+
+use egui::{Color32, Pos2, Stroke, Vec2};
+
+use crate::{
+    app::{INTERNAL_HEIGHT, INTERNAL_WIDTH},
+    graph::{Graph, NodeIndex},
+    painter::{Canvas, GraphPainter},
+};
+
+/// A [`Canvas`] that builds up an SVG document at our internal coordinates
+pub struct SvgCanvas {
+    body: String,
+}
+
+impl SvgCanvas {
+    pub fn new() -> Self {
+        SvgCanvas {
+            body: String::new(),
+        }
+    }
+
+    pub fn finish(self) -> String {
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{INTERNAL_WIDTH}\" \
+             height=\"{INTERNAL_HEIGHT}\" viewBox=\"0 0 {INTERNAL_WIDTH} {INTERNAL_HEIGHT}\">\n{}</svg>\n",
+            self.body
+        )
+    }
+}
+
+/// Turns a color into an svg paint attribute, adding an opacity if it needs one
+fn paint(attribute: &str, color: Color32) -> String {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+
+    if a == 0 {
+        format!("{attribute}=\"none\"")
+    } else if a == u8::MAX {
+        format!("{attribute}=\"#{r:02x}{g:02x}{b:02x}\"")
+    } else {
+        format!(
+            "{attribute}=\"#{r:02x}{g:02x}{b:02x}\" {attribute}-opacity=\"{}\"",
+            a as f32 / 255.0
+        )
+    }
+}
+
+fn stroke(stroke: Stroke) -> String {
+    if stroke.width <= 0.0 {
+        "stroke=\"none\"".to_owned()
+    } else {
+        format!(
+            "{} stroke-width=\"{}\"",
+            paint("stroke", stroke.color),
+            stroke.width
+        )
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+impl Canvas for SvgCanvas {
+    fn scale(&self) -> Vec2 {
+        Vec2::new(1.0, 1.0)
+    }
+
+    fn line_segment(&mut self, [a, b]: [Pos2; 2], line: Stroke) {
+        let _ = writeln!(
+            self.body,
+            "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {}/>",
+            a.x,
+            a.y,
+            b.x,
+            b.y,
+            stroke(line)
+        );
+    }
+
+    fn quadratic_bezier(&mut self, [a, control, b]: [Pos2; 3], line: Stroke) {
+        let _ = writeln!(
+            self.body,
+            "  <path d=\"M {} {} Q {} {} {} {}\" fill=\"none\" {}/>",
+            a.x,
+            a.y,
+            control.x,
+            control.y,
+            b.x,
+            b.y,
+            stroke(line)
+        );
+    }
+
+    fn circle(&mut self, center: Pos2, radius: f32, fill: Color32, line: Stroke) {
+        let _ = writeln!(
+            self.body,
+            "  <circle cx=\"{}\" cy=\"{}\" r=\"{radius}\" {} {}/>",
+            center.x,
+            center.y,
+            paint("fill", fill),
+            stroke(line)
+        );
+    }
+
+    fn text(
+        &mut self,
+        pos: Pos2,
+        text: &str,
+        size: f32,
+        color: Color32,
+        background: Option<Color32>,
+    ) {
+        if let Some(background) = background {
+            // We can't measure text without a font so guess based on monospace proportions
+            let width = 0.6 * size * text.chars().count() as f32;
+            let _ = writeln!(
+                self.body,
+                "  <rect x=\"{}\" y=\"{}\" width=\"{width}\" height=\"{size}\" {}/>",
+                pos.x - width / 2.0,
+                pos.y - size / 2.0,
+                paint("fill", background)
+            );
+        }
+
+        let _ = writeln!(
+            self.body,
+            "  <text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"{size}\" \
+             text-anchor=\"middle\" dominant-baseline=\"central\" {}>{}</text>",
+            pos.x,
+            pos.y,
+            paint("fill", color),
+            escape(text)
+        );
+    }
+}
+
+/// Draws a graph as an SVG image, including the path to `path_end` if there is one
+pub fn to_svg(graph: &Graph, painter: &GraphPainter, path_end: Option<NodeIndex>) -> String {
+    let mut canvas = SvgCanvas::new();
+
+    painter.paint_graph(graph, &mut canvas);
+    if let Some(end) = path_end {
+        painter.paint_path(end, graph, &mut canvas);
+    }
+
+    canvas.finish()
+}
@@ -0,0 +1,116 @@
+use egui::{DragValue, Ui, Widget};
+use rand::Rng;
+
+use crate::{
+    generation::{
+        circle_layout, new_seed, seed_ui, seeded_rng, GraphGenerator, RandomWeights,
+        MAX_GENERATED_NODES,
+    },
+    graph::Graph,
+};
+
+/// Makes a scale-free graph by preferential attachment, where a few nodes end up with far more
+/// edges than the rest
+///
+/// It starts with a few nodes all joined together, then each new node is joined to a set number
+/// of the nodes already there, picking nodes with more edges more often.
+pub struct BarabasiAlbertGenerator {
+    graph_name: String,
+    node_count: u32,
+    /// How many edges each new node comes with
+    edges_per_node: u32,
+    weights: RandomWeights,
+    seed: u64,
+}
+
+impl GraphGenerator for BarabasiAlbertGenerator {
+    fn name(&self) -> &'static str {
+        "Barabási–Albert Random Graph"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        seed_ui(ui, &mut self.seed);
+
+        ui.horizontal(|ui| {
+            ui.label("Node Count");
+            DragValue::new(&mut self.node_count)
+                .clamp_range(2..=MAX_GENERATED_NODES)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Edges Per New Node");
+            DragValue::new(&mut self.edges_per_node)
+                .clamp_range(1..=(self.node_count - 1).min(10))
+                .ui(ui);
+        });
+
+        self.weights.ui(ui);
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let mut rng = seeded_rng(self.seed);
+
+        let count = self.node_count as usize;
+        let per_node = (self.edges_per_node as usize).min(count - 1);
+
+        let mut edges = Vec::new();
+        // Every end of every edge, so picking from here picks nodes by how many edges they have
+        let mut ends = Vec::new();
+
+        // The first few nodes are all joined so they each have somewhere to be picked from
+        for a in 0..=per_node {
+            for b in 0..a {
+                edges.push((a, b));
+                ends.extend([a, b]);
+            }
+        }
+
+        for a in per_node + 1..count {
+            let mut targets = Vec::with_capacity(per_node);
+
+            while targets.len() < per_node {
+                let b = ends[rng.gen_range(0..ends.len() as u32) as usize];
+                if !targets.contains(&b) {
+                    targets.push(b);
+                }
+            }
+
+            for b in targets {
+                edges.push((a, b));
+                ends.extend([a, b]);
+            }
+        }
+
+        let mut graph = Graph::new(self.graph_name.clone(), false, self.weights.enabled);
+        graph.set_seed(Some(self.seed));
+
+        let ids = (0..count)
+            .map(|i| graph.add_node(circle_layout(i, count), i.to_string(), Vec::new()))
+            .collect::<Vec<_>>();
+
+        for (a, b) in edges {
+            graph.add_edge(ids[a], ids[b], self.weights.sample(&mut rng));
+        }
+
+        graph.reset();
+        Ok(graph)
+    }
+}
+
+impl Default for BarabasiAlbertGenerator {
+    fn default() -> Self {
+        BarabasiAlbertGenerator {
+            graph_name: String::new(),
+            node_count: 50,
+            edges_per_node: 2,
+            weights: RandomWeights::default(),
+            seed: new_seed(),
+        }
+    }
+}
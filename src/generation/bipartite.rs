@@ -0,0 +1,121 @@
+use egui::{DragValue, Ui, Widget};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    generation::{new_seed, seed_ui, seeded_rng, GraphGenerator},
+    graph::Graph,
+};
+
+/// Makes a random graph with two columns of nodes where edges only go between the columns
+pub struct RandomBipartiteGenerator {
+    graph_name: String,
+    left_count: u8,
+    right_count: u8,
+    edge_count: u16,
+    weights: bool,
+    weight_lower_bound: f32,
+    weight_upper_bound: f32,
+    seed: u64,
+}
+
+impl GraphGenerator for RandomBipartiteGenerator {
+    fn name(&self) -> &'static str {
+        "Random Bipartite Graph"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        seed_ui(ui, &mut self.seed);
+
+        ui.horizontal(|ui| {
+            ui.label("Left Nodes");
+            DragValue::new(&mut self.left_count)
+                .clamp_range(1..=30)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Right Nodes");
+            DragValue::new(&mut self.right_count)
+                .clamp_range(1..=30)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Edge Count");
+            DragValue::new(&mut self.edge_count)
+                .clamp_range(0..=self.left_count as u16 * self.right_count as u16)
+                .ui(ui);
+        });
+
+        ui.checkbox(&mut self.weights, "Weighted Graph");
+
+        if self.weights {
+            DragValue::new(&mut self.weight_lower_bound).ui(ui);
+
+            if self.weight_upper_bound < self.weight_lower_bound {
+                self.weight_upper_bound = self.weight_lower_bound;
+            }
+
+            DragValue::new(&mut self.weight_upper_bound)
+                .clamp_range(self.weight_lower_bound..=f32::INFINITY)
+                .ui(ui);
+        }
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let mut graph = Graph::new(self.graph_name.clone(), false, self.weights);
+        let mut rng = seeded_rng(self.seed);
+        graph.set_seed(Some(self.seed));
+
+        // Each side is spread out evenly down its own column
+        let column = |graph: &mut Graph, count: u8, x: f32, prefix: &str| {
+            (0..count)
+                .map(|i| {
+                    let y = 1000.0 * (i as f32 + 0.5) / count as f32;
+                    graph.add_node((x, y), format!("{prefix}{i}"), Vec::new())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let left = column(&mut graph, self.left_count, 250.0, "L");
+        let right = column(&mut graph, self.right_count, 750.0, "R");
+
+        let mut pairs = left
+            .iter()
+            .flat_map(|a| right.iter().map(move |b| (*a, *b)))
+            .collect::<Vec<_>>();
+        pairs.shuffle(&mut rng);
+
+        for (a, b) in pairs.into_iter().take(self.edge_count as usize) {
+            let weight = if self.weights {
+                Some(rng.gen_range(self.weight_lower_bound..=self.weight_upper_bound))
+            } else {
+                None
+            };
+
+            graph.add_edge(a, b, weight);
+        }
+
+        Ok(graph)
+    }
+}
+
+impl Default for RandomBipartiteGenerator {
+    fn default() -> Self {
+        RandomBipartiteGenerator {
+            graph_name: String::new(),
+            left_count: 4,
+            right_count: 4,
+            edge_count: 6,
+            weights: false,
+            weight_lower_bound: 1.0,
+            weight_upper_bound: 5.0,
+            seed: new_seed(),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use egui::{DragValue, Ui, Widget};
+use rand::Rng;
+
+use crate::{
+    generation::{
+        new_seed, seed_ui, seeded_rng, GraphGenerator, RandomWeights, MAX_GENERATED_EDGES,
+    },
+    graph::Graph,
+};
+
+/// The most nodes a DAG can have, every pair of nodes is tried for an edge so this is kept
+/// lower than for the other generators
+const MAX_NODES: u32 = 1000;
+
+/// Makes a random directed acyclic graph with its nodes in layers from top to bottom
+///
+/// Every edge goes down to a later layer so there can't be any cycles. Each node past the first
+/// layer gets an edge from the layer just above it, so no layer could be any higher, and then
+/// every other pair gets an edge with the edge density as its chance.
+pub struct RandomDagGenerator {
+    graph_name: String,
+    node_count: u32,
+    layer_count: u32,
+    edge_density: f64,
+    /// Whether the extra edges can go further down than the next layer
+    skip_layers: bool,
+    weights: RandomWeights,
+    seed: u64,
+}
+
+impl GraphGenerator for RandomDagGenerator {
+    fn name(&self) -> &'static str {
+        "Random DAG"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        seed_ui(ui, &mut self.seed);
+
+        ui.horizontal(|ui| {
+            ui.label("Node Count");
+            DragValue::new(&mut self.node_count)
+                .clamp_range(1..=MAX_NODES)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Layers");
+            DragValue::new(&mut self.layer_count)
+                .clamp_range(1..=self.node_count)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Edge Density");
+            DragValue::new(&mut self.edge_density)
+                .clamp_range(0.0..=1.0)
+                .speed(0.01)
+                .ui(ui);
+        });
+
+        ui.checkbox(&mut self.skip_layers, "Edges Can Skip Layers");
+        self.weights.ui(ui);
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let mut rng = seeded_rng(self.seed);
+
+        let count = self.node_count as usize;
+        let layer_count = (self.layer_count as usize).min(count);
+
+        // Every layer needs a node, the rest are spread out at random
+        let mut widths = vec![1; layer_count];
+        for _ in layer_count..count {
+            widths[rng.gen_range(0..layer_count as u32) as usize] += 1;
+        }
+
+        let mut layers = Vec::with_capacity(layer_count);
+        let mut next = 0;
+        for width in &widths {
+            layers.push((next..next + width).collect::<Vec<_>>());
+            next += width;
+        }
+
+        // The layers the extra edges from a layer can go down to
+        let lower_layers = |i: usize| {
+            let last = if self.skip_layers {
+                layer_count
+            } else {
+                (i + 2).min(layer_count)
+            };
+            (i + 1).min(last)..last
+        };
+
+        let possible = (0..layer_count)
+            .flat_map(|i| lower_layers(i).map(move |j| (i, j)))
+            .map(|(i, j)| widths[i] * widths[j])
+            .sum::<usize>();
+
+        let expected = self.edge_density * possible as f64;
+        if expected > MAX_GENERATED_EDGES as f64 {
+            return Err(format!(
+                "That would make about {expected:.0} edges, but at most {MAX_GENERATED_EDGES} \
+                 can be drawn"
+            ));
+        }
+
+        let mut edges = Vec::new();
+        // The node each node got its edge from the layer above from
+        let mut parents = vec![None; count];
+
+        for (i, layer) in layers.iter().enumerate().skip(1) {
+            let above = &layers[i - 1];
+
+            for b in layer {
+                let a = above[rng.gen_range(0..above.len() as u32) as usize];
+                edges.push((a, *b));
+                parents[*b] = Some(a);
+            }
+        }
+
+        for (i, layer) in layers.iter().enumerate() {
+            for lower in &layers[lower_layers(i)] {
+                for a in layer {
+                    for b in lower {
+                        if rng.gen_bool(self.edge_density) && parents[*b] != Some(*a) {
+                            edges.push((*a, *b));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut graph = Graph::new(self.graph_name.clone(), true, self.weights.enabled);
+        graph.set_seed(Some(self.seed));
+
+        let height = (layer_count - 1).max(1) as f32;
+        let mut ids = Vec::with_capacity(count);
+        for (i, layer) in layers.iter().enumerate() {
+            for (j, node) in layer.iter().enumerate() {
+                let pos = (
+                    1000.0 * (j as f32 + 0.5) / layer.len() as f32,
+                    50.0 + 900.0 * i as f32 / height,
+                );
+                ids.push(graph.add_node(pos, node.to_string(), Vec::new()));
+            }
+        }
+
+        for (a, b) in edges {
+            graph.add_edge(ids[a], ids[b], self.weights.sample(&mut rng));
+        }
+
+        graph.reset();
+        Ok(graph)
+    }
+}
+
+impl Default for RandomDagGenerator {
+    fn default() -> Self {
+        RandomDagGenerator {
+            graph_name: String::new(),
+            node_count: 12,
+            layer_count: 4,
+            edge_density: 0.2,
+            skip_layers: true,
+            weights: RandomWeights::default(),
+            seed: new_seed(),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use egui::{TextEdit, Ui};
+
+use crate::{
+    files::{self, FileLoader},
+    formats::dot::from_dot,
+    generation::GraphGenerator,
+    graph::Graph,
+};
+
+/// Builds a graph from Graphviz DOT source, either typed in or read from a file
+#[derive(Default)]
+pub struct DotGraphGenerator {
+    source: String,
+    file_path: String,
+    file_loader: FileLoader,
+    file_error: Option<String>,
+}
+
+impl GraphGenerator for DotGraphGenerator {
+    fn name(&self) -> &'static str {
+        "Import DOT"
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let graph = from_dot(&self.source).map_err(|e| e.to_string())?;
+        *self = Self::default();
+        Ok(graph)
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            if files::USES_PATHS {
+                ui.label("File");
+                TextEdit::singleline(&mut self.file_path)
+                    .hint_text("graph.dot")
+                    .desired_width(150.0)
+                    .show(ui);
+            }
+
+            if ui.button("Open").clicked() {
+                self.file_error = self
+                    .file_loader
+                    .open(ui.ctx(), &self.file_path, ".dot,.gv")
+                    .err();
+            }
+        });
+
+        if let Some(file) = self.file_loader.take() {
+            self.source = file.contents;
+        }
+
+        if let Some(error) = &self.file_error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+
+        ui.label("DOT Source");
+        TextEdit::multiline(&mut self.source)
+            .code_editor()
+            .hint_text("digraph { a -> b -> c }")
+            .desired_rows(8)
+            .show(ui);
+    }
+}
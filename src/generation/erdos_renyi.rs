@@ -0,0 +1,230 @@
+use egui::{ComboBox, DragValue, Ui, Widget};
+use rand::{seq::index, Rng};
+
+use crate::{
+    generation::{
+        circle_layout, new_seed, seed_ui, seeded_rng, GraphGenerator, RandomWeights,
+        MAX_GENERATED_EDGES, MAX_GENERATED_NODES,
+    },
+    graph::Graph,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ErdosRenyiModel {
+    /// G(n, p), every pair of nodes gets an edge with the same chance
+    Probability,
+    /// G(n, m), a set number of edges are put between pairs of nodes picked uniformly
+    EdgeCount,
+}
+
+impl ErdosRenyiModel {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            ErdosRenyiModel::Probability => "G(n, p) Edge Probability",
+            ErdosRenyiModel::EdgeCount => "G(n, m) Edge Count",
+        }
+    }
+
+    pub const fn values() -> [ErdosRenyiModel; 2] {
+        [ErdosRenyiModel::Probability, ErdosRenyiModel::EdgeCount]
+    }
+}
+
+/// Makes an Erdős–Rényi random graph with its nodes around a circle
+pub struct ErdosRenyiGenerator {
+    graph_name: String,
+    model: ErdosRenyiModel,
+    directed: bool,
+    node_count: u32,
+    probability: f64,
+    edge_count: u32,
+    weights: RandomWeights,
+    seed: u64,
+}
+
+impl ErdosRenyiGenerator {
+    /// How many edges the graph could have without any loops or repeats
+    fn pair_count(&self) -> u64 {
+        let n = self.node_count as u64;
+        let pairs = n * n.saturating_sub(1);
+
+        if self.directed {
+            pairs
+        } else {
+            pairs / 2
+        }
+    }
+
+    /// Turns one of the numbers from `0..pair_count` into the pair of nodes it stands for
+    fn pair(&self, k: usize) -> (usize, usize) {
+        let n = self.node_count as usize;
+
+        if self.directed {
+            // Each node has a row of the nodes it can go to, which leaves itself out
+            let (a, b) = (k / (n - 1), k % (n - 1));
+            (a, if b >= a { b + 1 } else { b })
+        } else {
+            // Row `a` holds the `a` nodes before it, so it starts at the `a - 1`th triangle number
+            let mut a = ((1.0 + (1.0 + 8.0 * k as f64).sqrt()) / 2.0) as usize;
+            while a * (a - 1) / 2 > k {
+                a -= 1;
+            }
+            while (a + 1) * a / 2 <= k {
+                a += 1;
+            }
+            (a, k - a * (a - 1) / 2)
+        }
+    }
+
+    /// Picks how many pairs to pass over before the next one with an edge, which follows a
+    /// geometric distribution
+    fn skip(&self, rng: &mut impl Rng) -> usize {
+        if self.probability >= 1.0 {
+            return 0;
+        }
+
+        let r: f64 = rng.gen();
+        ((1.0 - r).ln() / (1.0 - self.probability).ln()) as usize
+    }
+
+    /// Tries every pair of nodes, jumping straight to the next one that gets an edge so sparse
+    /// graphs don't take time for every pair
+    fn probability_edges(&self, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+        let n = self.node_count as usize;
+        let mut edges = Vec::new();
+
+        if self.probability <= 0.0 {
+            return edges;
+        }
+
+        for a in 0..n {
+            // Undirected pairs are only tried from their later node so they're not tried twice
+            let row = if self.directed { n - 1 } else { a };
+
+            let mut b = self.skip(rng);
+            while b < row {
+                edges.push((a, if self.directed && b >= a { b + 1 } else { b }));
+                b = b.saturating_add(1).saturating_add(self.skip(rng));
+            }
+        }
+
+        edges
+    }
+}
+
+impl GraphGenerator for ErdosRenyiGenerator {
+    fn name(&self) -> &'static str {
+        "Erdős–Rényi Random Graph"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        seed_ui(ui, &mut self.seed);
+
+        let models = ErdosRenyiModel::values();
+        let mut curr_model = models.iter().position(|m| *m == self.model).unwrap();
+
+        ComboBox::from_label("Model").show_index(ui, &mut curr_model, models.len(), |i| {
+            models[i].name().to_owned()
+        });
+
+        self.model = models[curr_model];
+
+        ui.horizontal(|ui| {
+            ui.label("Node Count");
+            DragValue::new(&mut self.node_count)
+                .clamp_range(1..=MAX_GENERATED_NODES)
+                .ui(ui);
+        });
+
+        match self.model {
+            ErdosRenyiModel::Probability => {
+                ui.horizontal(|ui| {
+                    ui.label("Edge Probability");
+                    DragValue::new(&mut self.probability)
+                        .clamp_range(0.0..=1.0)
+                        .speed(0.001)
+                        .ui(ui);
+                });
+
+                ui.label(format!(
+                    "Expect about {:.0} edges",
+                    self.probability * self.pair_count() as f64
+                ));
+            }
+            ErdosRenyiModel::EdgeCount => {
+                let max_edges = self.pair_count().min(MAX_GENERATED_EDGES as u64);
+
+                ui.horizontal(|ui| {
+                    ui.label("Edge Count");
+                    DragValue::new(&mut self.edge_count)
+                        .clamp_range(0..=max_edges)
+                        .ui(ui);
+                });
+            }
+        }
+
+        ui.checkbox(&mut self.directed, "Directed Graph");
+        self.weights.ui(ui);
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let mut rng = seeded_rng(self.seed);
+
+        let edges = match self.model {
+            ErdosRenyiModel::Probability => {
+                let expected = self.probability * self.pair_count() as f64;
+                if expected > MAX_GENERATED_EDGES as f64 {
+                    return Err(format!(
+                        "That would make about {expected:.0} edges, but at most \
+                         {MAX_GENERATED_EDGES} can be drawn"
+                    ));
+                }
+
+                self.probability_edges(&mut rng)
+            }
+            ErdosRenyiModel::EdgeCount => {
+                let edge_count = (self.edge_count as u64).min(self.pair_count());
+
+                index::sample(&mut rng, self.pair_count() as usize, edge_count as usize)
+                    .into_iter()
+                    .map(|k| self.pair(k))
+                    .collect()
+            }
+        };
+
+        let mut graph = Graph::new(self.graph_name.clone(), self.directed, self.weights.enabled);
+        graph.set_seed(Some(self.seed));
+
+        let count = self.node_count as usize;
+        let ids = (0..count)
+            .map(|i| graph.add_node(circle_layout(i, count), i.to_string(), Vec::new()))
+            .collect::<Vec<_>>();
+
+        for (a, b) in edges {
+            graph.add_edge(ids[a], ids[b], self.weights.sample(&mut rng));
+        }
+
+        graph.reset();
+        Ok(graph)
+    }
+}
+
+impl Default for ErdosRenyiGenerator {
+    fn default() -> Self {
+        ErdosRenyiGenerator {
+            graph_name: String::new(),
+            model: ErdosRenyiModel::Probability,
+            directed: false,
+            node_count: 50,
+            probability: 0.05,
+            edge_count: 60,
+            weights: RandomWeights::default(),
+            seed: new_seed(),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use std::ops::RangeInclusive;
+
+use egui::{ComboBox, DragValue, Ui, Widget};
+
+use crate::{
+    generation::{circle_layout, fit_layout, GraphGenerator, GraphLayout, MAX_GENERATED_NODES},
+    graph::Graph,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum GraphFamily {
+    Path,
+    Cycle,
+    Complete,
+    CompleteBipartite,
+    Star,
+    Wheel,
+    Hypercube,
+}
+
+impl GraphFamily {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            GraphFamily::Path => "Path",
+            GraphFamily::Cycle => "Cycle",
+            GraphFamily::Complete => "Complete",
+            GraphFamily::CompleteBipartite => "Complete Bipartite",
+            GraphFamily::Star => "Star",
+            GraphFamily::Wheel => "Wheel",
+            GraphFamily::Hypercube => "Hypercube",
+        }
+    }
+
+    pub const fn values() -> [GraphFamily; 7] {
+        [
+            GraphFamily::Path,
+            GraphFamily::Cycle,
+            GraphFamily::Complete,
+            GraphFamily::CompleteBipartite,
+            GraphFamily::Star,
+            GraphFamily::Wheel,
+            GraphFamily::Hypercube,
+        ]
+    }
+
+    /// What the number picked for the family's size counts
+    const fn count_label(&self) -> &'static str {
+        match self {
+            GraphFamily::Path | GraphFamily::Cycle | GraphFamily::Complete => "Nodes",
+            GraphFamily::CompleteBipartite => "Left Nodes",
+            GraphFamily::Star => "Leaves",
+            GraphFamily::Wheel => "Spokes",
+            GraphFamily::Hypercube => "Dimensions",
+        }
+    }
+
+    fn count_range(&self) -> RangeInclusive<u32> {
+        match self {
+            GraphFamily::Path | GraphFamily::Star => 1..=MAX_GENERATED_NODES,
+            GraphFamily::Cycle | GraphFamily::Wheel => 3..=MAX_GENERATED_NODES,
+            // Any bigger and there are too many edges to draw
+            GraphFamily::Complete | GraphFamily::CompleteBipartite => 1..=100,
+            GraphFamily::Hypercube => 1..=10,
+        }
+    }
+}
+
+/// Makes one of the common families of graphs that come in any size, laid out the way they're
+/// usually drawn
+///
+/// In directed graphs edges go from the node listed first to the one listed later, apart from
+/// the edge closing a cycle which goes back to the first node.
+pub struct FamilyGenerator {
+    graph_name: String,
+    family: GraphFamily,
+    count: u32,
+    /// How many nodes go on the right of a complete bipartite graph
+    right_count: u32,
+    directed: bool,
+}
+
+impl FamilyGenerator {
+    /// Lays out the nodes and picks the edges between them
+    fn generate(&self) -> GraphLayout {
+        let n = self.count as usize;
+
+        match self.family {
+            GraphFamily::Path => {
+                let positions = (0..n)
+                    .map(|i| (50.0 + 900.0 * i as f32 / (n - 1).max(1) as f32, 500.0))
+                    .collect();
+                let edges = (1..n).map(|i| (i - 1, i)).collect();
+
+                GraphLayout::numbered(positions, edges)
+            }
+            GraphFamily::Cycle => {
+                let positions = (0..n).map(|i| circle_layout(i, n)).collect();
+                let edges = (0..n).map(|i| (i, (i + 1) % n)).collect();
+
+                GraphLayout::numbered(positions, edges)
+            }
+            GraphFamily::Complete => {
+                let positions = (0..n).map(|i| circle_layout(i, n)).collect();
+                let edges = (0..n)
+                    .flat_map(|a| (a + 1..n).map(move |b| (a, b)))
+                    .collect();
+
+                GraphLayout::numbered(positions, edges)
+            }
+            GraphFamily::CompleteBipartite => {
+                let m = self.right_count as usize;
+
+                // Each side is spread out evenly down its own column
+                let column = |count: usize, x: f32, prefix: &str| {
+                    (0..count)
+                        .map(|i| {
+                            let y = 1000.0 * (i as f32 + 0.5) / count as f32;
+                            ((x, y), format!("{prefix}{i}"))
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                let mut nodes = column(n, 250.0, "L");
+                nodes.extend(column(m, 750.0, "R"));
+                let edges = (0..n)
+                    .flat_map(|a| (n..n + m).map(move |b| (a, b)))
+                    .collect();
+
+                GraphLayout { nodes, edges }
+            }
+            GraphFamily::Star | GraphFamily::Wheel => {
+                let mut positions = vec![(500.0, 500.0)];
+                positions.extend((0..n).map(|i| circle_layout(i, n)));
+
+                let mut edges = (1..=n).map(|i| (0, i)).collect::<Vec<_>>();
+                if self.family == GraphFamily::Wheel {
+                    edges.extend((1..=n).map(|i| (i, i % n + 1)));
+                }
+
+                GraphLayout::numbered(positions, edges)
+            }
+            GraphFamily::Hypercube => {
+                // Each dimension moves the node a different way, skewed a little so no two
+                // nodes end up in the same place
+                let directions = (0..n)
+                    .map(|d| {
+                        let angle = std::f32::consts::PI * d as f32 / n as f32;
+                        let length = 1.0 + 0.5 * d as f32 / n as f32;
+                        (length * angle.cos(), length * angle.sin())
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut positions = (0..1usize << n)
+                    .map(|i| {
+                        directions
+                            .iter()
+                            .enumerate()
+                            .filter(|(d, _)| i & (1 << d) != 0)
+                            .fold((0.0, 0.0), |(x, y), (_, (dx, dy))| (x + dx, y + dy))
+                    })
+                    .collect::<Vec<_>>();
+                fit_layout(&mut positions);
+
+                // Nodes are named by their coordinates, so neighbors differ in one digit
+                let nodes = positions
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, pos)| (pos, format!("{i:0n$b}")))
+                    .collect();
+                let edges = (0..1usize << n)
+                    .flat_map(|a| (0..n).map(move |d| (a, a ^ (1 << d))))
+                    .filter(|(a, b)| a < b)
+                    .collect();
+
+                GraphLayout { nodes, edges }
+            }
+        }
+    }
+}
+
+impl GraphGenerator for FamilyGenerator {
+    fn name(&self) -> &'static str {
+        "Graph Family"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        let families = GraphFamily::values();
+        let mut curr_family = families.iter().position(|f| *f == self.family).unwrap();
+
+        ComboBox::from_label("Family").show_index(ui, &mut curr_family, families.len(), |i| {
+            families[i].name().to_owned()
+        });
+
+        self.family = families[curr_family];
+
+        ui.horizontal(|ui| {
+            ui.label(self.family.count_label());
+            DragValue::new(&mut self.count)
+                .clamp_range(self.family.count_range())
+                .ui(ui);
+        });
+
+        if self.family == GraphFamily::CompleteBipartite {
+            ui.horizontal(|ui| {
+                ui.label("Right Nodes");
+                DragValue::new(&mut self.right_count)
+                    .clamp_range(1..=100)
+                    .ui(ui);
+            });
+        }
+
+        ui.checkbox(&mut self.directed, "Directed Graph");
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        Ok(self
+            .generate()
+            .build(self.graph_name.clone(), self.directed))
+    }
+}
+
+impl Default for FamilyGenerator {
+    fn default() -> Self {
+        FamilyGenerator {
+            graph_name: String::new(),
+            family: GraphFamily::Path,
+            count: 5,
+            right_count: 3,
+            directed: false,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use egui::{DragValue, Ui, Widget};
+
+use crate::{
+    generation::{GraphGenerator, GraphLayout},
+    graph::Graph,
+};
+
+/// The most rows or columns a grid can have
+pub const MAX_SIDE: usize = 70;
+
+/// Lays out `rows` by `columns` nodes in a grid filling the usual space, naming each one by its
+/// row and column
+pub fn grid_nodes(rows: usize, columns: usize) -> Vec<((f32, f32), String)> {
+    let spacing = 900.0 / (rows.max(columns) - 1).max(1) as f32;
+    // Narrow grids are kept in the middle
+    let offset = (
+        500.0 - spacing * (columns - 1) as f32 / 2.0,
+        500.0 - spacing * (rows - 1) as f32 / 2.0,
+    );
+
+    (0..rows)
+        .flat_map(|r| (0..columns).map(move |c| (r, c)))
+        .map(|(r, c)| {
+            let pos = (offset.0 + spacing * c as f32, offset.1 + spacing * r as f32);
+            (pos, format!("{r},{c}"))
+        })
+        .collect()
+}
+
+/// Makes a grid of nodes, each joined to the ones next to it
+///
+/// Diagonals join each node to the ones at its corners as well. Wrapping around joins each
+/// edge of the grid to the opposite one, making a torus, as long as that side has at least 3
+/// nodes so it doesn't repeat an edge.
+pub struct GridGenerator {
+    graph_name: String,
+    rows: usize,
+    columns: usize,
+    diagonals: bool,
+    wrap_around: bool,
+    directed: bool,
+}
+
+impl GridGenerator {
+    fn edges(&self) -> Vec<(usize, usize)> {
+        let (rows, columns) = (self.rows, self.columns);
+        let wrap_rows = self.wrap_around && rows >= 3;
+        let wrap_columns = self.wrap_around && columns >= 3;
+
+        // Finds the node `dr` rows down and `dc` columns right, if there is one
+        let offset = |r: usize, c: usize, dr: isize, dc: isize| {
+            let r = r as isize + dr;
+            let c = c as isize + dc;
+
+            let r = match r {
+                r if (0..rows as isize).contains(&r) => r as usize,
+                _ if wrap_rows => r.rem_euclid(rows as isize) as usize,
+                _ => return None,
+            };
+            let c = match c {
+                c if (0..columns as isize).contains(&c) => c as usize,
+                _ if wrap_columns => c.rem_euclid(columns as isize) as usize,
+                _ => return None,
+            };
+
+            Some(r * columns + c)
+        };
+
+        let mut directions = vec![(0, 1), (1, 0)];
+        if self.diagonals {
+            directions.extend([(1, 1), (1, -1)]);
+        }
+
+        let mut edges = Vec::new();
+        for r in 0..rows {
+            for c in 0..columns {
+                for (dr, dc) in &directions {
+                    if let Some(b) = offset(r, c, *dr, *dc) {
+                        edges.push((r * columns + c, b));
+                    }
+                }
+            }
+        }
+
+        edges
+    }
+}
+
+impl GraphGenerator for GridGenerator {
+    fn name(&self) -> &'static str {
+        "Grid Graph"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Rows");
+            DragValue::new(&mut self.rows)
+                .clamp_range(1..=MAX_SIDE)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Columns");
+            DragValue::new(&mut self.columns)
+                .clamp_range(1..=MAX_SIDE)
+                .ui(ui);
+        });
+
+        ui.checkbox(&mut self.diagonals, "Diagonals");
+        ui.checkbox(&mut self.wrap_around, "Wrap Around (Torus)");
+        ui.checkbox(&mut self.directed, "Directed Graph");
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let layout = GraphLayout {
+            nodes: grid_nodes(self.rows, self.columns),
+            edges: self.edges(),
+        };
+
+        Ok(layout.build(self.graph_name.clone(), self.directed))
+    }
+}
+
+impl Default for GridGenerator {
+    fn default() -> Self {
+        GridGenerator {
+            graph_name: String::new(),
+            rows: 4,
+            columns: 5,
+            diagonals: false,
+            wrap_around: false,
+            directed: false,
+        }
+    }
+}
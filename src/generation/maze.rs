@@ -0,0 +1,279 @@
+use std::collections::HashSet;
+
+use egui::{ComboBox, DragValue, Ui, Widget};
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    generation::{
+        grid::{grid_nodes, MAX_SIDE},
+        new_seed, seed_ui, seeded_rng, GraphGenerator, GraphLayout,
+    },
+    graph::Graph,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MazeAlgorithm {
+    /// Wanders off at random and backs up when it gets stuck, making long winding passages
+    Backtracker,
+    /// Grows out from a corner a random wall at a time, making lots of short dead ends
+    Prim,
+    /// Knocks down random walls between cells that aren't joined yet
+    Kruskal,
+}
+
+impl MazeAlgorithm {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            MazeAlgorithm::Backtracker => "Recursive Backtracker",
+            MazeAlgorithm::Prim => "Prim's",
+            MazeAlgorithm::Kruskal => "Kruskal's",
+        }
+    }
+
+    pub const fn values() -> [MazeAlgorithm; 3] {
+        [
+            MazeAlgorithm::Backtracker,
+            MazeAlgorithm::Prim,
+            MazeAlgorithm::Kruskal,
+        ]
+    }
+}
+
+/// Makes a maze on a grid, where each node is a cell and each edge is a passage between cells
+///
+/// Each maze has exactly one way between any two cells, unless extra passages are knocked
+/// through to make loops.
+pub struct MazeGenerator {
+    graph_name: String,
+    algorithm: MazeAlgorithm,
+    rows: usize,
+    columns: usize,
+    /// The chance each wall left standing is knocked down afterwards
+    extra_passages: f64,
+    seed: u64,
+}
+
+impl MazeGenerator {
+    /// Every wall between two cells next to each other
+    fn walls(&self) -> Vec<(usize, usize)> {
+        let (rows, columns) = (self.rows, self.columns);
+        let mut walls = Vec::new();
+
+        for r in 0..rows {
+            for c in 0..columns {
+                let cell = r * columns + c;
+                if c + 1 < columns {
+                    walls.push((cell, cell + 1));
+                }
+                if r + 1 < rows {
+                    walls.push((cell, cell + columns));
+                }
+            }
+        }
+
+        walls
+    }
+
+    fn neighbors(&self, cell: usize) -> Vec<usize> {
+        let (r, c) = (cell / self.columns, cell % self.columns);
+        let mut neighbors = Vec::with_capacity(4);
+
+        if r > 0 {
+            neighbors.push(cell - self.columns);
+        }
+        if c + 1 < self.columns {
+            neighbors.push(cell + 1);
+        }
+        if r + 1 < self.rows {
+            neighbors.push(cell + self.columns);
+        }
+        if c > 0 {
+            neighbors.push(cell - 1);
+        }
+
+        neighbors
+    }
+
+    fn backtracker(&self, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+        let mut visited = vec![false; self.rows * self.columns];
+        let mut passages = Vec::new();
+        let mut stack = vec![0];
+        visited[0] = true;
+
+        while let Some(cell) = stack.last().copied() {
+            let unvisited = self
+                .neighbors(cell)
+                .into_iter()
+                .filter(|n| !visited[*n])
+                .collect::<Vec<_>>();
+
+            let Some(next) = unvisited.choose(rng).copied() else {
+                stack.pop();
+                continue;
+            };
+
+            visited[next] = true;
+            passages.push((cell, next));
+            stack.push(next);
+        }
+
+        passages
+    }
+
+    fn prim(&self, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+        let mut in_maze = vec![false; self.rows * self.columns];
+        let mut passages = Vec::new();
+        // Walls between a cell in the maze and one that might not be yet
+        let mut frontier = self
+            .neighbors(0)
+            .into_iter()
+            .map(|n| (0, n))
+            .collect::<Vec<_>>();
+        in_maze[0] = true;
+
+        while !frontier.is_empty() {
+            let (cell, next) =
+                frontier.swap_remove(rng.gen_range(0..frontier.len() as u32) as usize);
+            if in_maze[next] {
+                continue;
+            }
+
+            in_maze[next] = true;
+            passages.push((cell, next));
+            frontier.extend(
+                self.neighbors(next)
+                    .into_iter()
+                    .filter(|n| !in_maze[*n])
+                    .map(|n| (next, n)),
+            );
+        }
+
+        passages
+    }
+
+    fn kruskal(&self, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+        let mut walls = self.walls();
+        walls.shuffle(rng);
+
+        // Each cell points towards another cell joined to it, ending at the one that stands
+        // for all of them
+        let mut parents = (0..self.rows * self.columns).collect::<Vec<_>>();
+        let find = |parents: &mut Vec<usize>, mut cell: usize| {
+            while parents[cell] != cell {
+                parents[cell] = parents[parents[cell]];
+                cell = parents[cell];
+            }
+            cell
+        };
+
+        walls
+            .into_iter()
+            .filter(|(a, b)| {
+                let (a, b) = (find(&mut parents, *a), find(&mut parents, *b));
+                if a == b {
+                    return false;
+                }
+
+                parents[a] = b;
+                true
+            })
+            .collect()
+    }
+}
+
+impl GraphGenerator for MazeGenerator {
+    fn name(&self) -> &'static str {
+        "Maze"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        seed_ui(ui, &mut self.seed);
+
+        let algorithms = MazeAlgorithm::values();
+        let mut curr_algorithm = algorithms
+            .iter()
+            .position(|a| *a == self.algorithm)
+            .unwrap();
+
+        ComboBox::from_label("Algorithm").show_index(
+            ui,
+            &mut curr_algorithm,
+            algorithms.len(),
+            |i| algorithms[i].name().to_owned(),
+        );
+
+        self.algorithm = algorithms[curr_algorithm];
+
+        ui.horizontal(|ui| {
+            ui.label("Rows");
+            DragValue::new(&mut self.rows)
+                .clamp_range(1..=MAX_SIDE)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Columns");
+            DragValue::new(&mut self.columns)
+                .clamp_range(1..=MAX_SIDE)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Extra Passages");
+            DragValue::new(&mut self.extra_passages)
+                .clamp_range(0.0..=1.0)
+                .speed(0.01)
+                .ui(ui);
+        });
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let mut rng = seeded_rng(self.seed);
+
+        let mut passages = match self.algorithm {
+            MazeAlgorithm::Backtracker => self.backtracker(&mut rng),
+            MazeAlgorithm::Prim => self.prim(&mut rng),
+            MazeAlgorithm::Kruskal => self.kruskal(&mut rng),
+        };
+
+        if self.extra_passages > 0.0 {
+            let carved = passages
+                .iter()
+                .map(|(a, b)| (*a.min(b), *a.max(b)))
+                .collect::<HashSet<_>>();
+
+            for wall in self.walls() {
+                if !carved.contains(&wall) && rng.gen_bool(self.extra_passages) {
+                    passages.push(wall);
+                }
+            }
+        }
+
+        let layout = GraphLayout {
+            nodes: grid_nodes(self.rows, self.columns),
+            edges: passages,
+        };
+
+        let mut graph = layout.build(self.graph_name.clone(), false);
+        graph.set_seed(Some(self.seed));
+        Ok(graph)
+    }
+}
+
+impl Default for MazeGenerator {
+    fn default() -> Self {
+        MazeGenerator {
+            graph_name: String::new(),
+            algorithm: MazeAlgorithm::Backtracker,
+            rows: 10,
+            columns: 10,
+            extra_passages: 0.0,
+            seed: new_seed(),
+        }
+    }
+}
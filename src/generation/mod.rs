@@ -1,21 +1,195 @@
+pub mod barabasi_albert;
+pub mod bipartite;
+pub mod dag;
+pub mod dot;
+pub mod erdos_renyi;
+pub mod families;
+pub mod grid;
+pub mod maze;
+pub mod named;
 pub mod random;
+pub mod trees;
+pub mod watts_strogatz;
 
-use egui::{TextBuffer, TextEdit, Ui};
+use egui::{DragValue, TextBuffer, TextEdit, Ui, Widget};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 
-use crate::{generation::random::RandomGraphMenu, graph::Graph};
+use crate::{
+    generation::{
+        barabasi_albert::BarabasiAlbertGenerator, bipartite::RandomBipartiteGenerator,
+        dag::RandomDagGenerator, dot::DotGraphGenerator, erdos_renyi::ErdosRenyiGenerator,
+        families::FamilyGenerator, grid::GridGenerator, maze::MazeGenerator,
+        named::NamedGraphGenerator, random::RandomGraphMenu, trees::RandomTreeGenerator,
+        watts_strogatz::WattsStrogatzGenerator,
+    },
+    graph::Graph,
+};
 
-pub const GENERATOR_COUNT: usize = 2;
+pub const GENERATOR_COUNT: usize = 13;
 
 pub fn generators() -> [Box<dyn GraphGenerator>; GENERATOR_COUNT] {
     [
         Box::new(EmptyGraphGenerator::default()),
+        Box::new(FamilyGenerator::default()),
+        Box::new(GridGenerator::default()),
+        Box::new(MazeGenerator::default()),
+        Box::new(NamedGraphGenerator::default()),
         Box::new(RandomGraphMenu::default()),
+        Box::new(RandomBipartiteGenerator::default()),
+        Box::new(ErdosRenyiGenerator::default()),
+        Box::new(BarabasiAlbertGenerator::default()),
+        Box::new(WattsStrogatzGenerator::default()),
+        Box::new(RandomTreeGenerator::default()),
+        Box::new(RandomDagGenerator::default()),
+        Box::new(DotGraphGenerator::default()),
     ]
 }
 
+/// Picks a new seed, kept small enough to be easy to read out and type back in
+pub fn new_seed() -> u64 {
+    rand::thread_rng().gen::<u32>() as u64
+}
+
+/// The random number generator random graphs are made with
+///
+/// ChaCha gives the same numbers for the same seed on every platform, so a graph made from a
+/// seed on the web is the same as one made from it natively.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Shows the seed a generator will use, along with a button to pick a new one
+pub fn seed_ui(ui: &mut Ui, seed: &mut u64) {
+    ui.horizontal(|ui| {
+        ui.label("Seed");
+        DragValue::new(seed).clamp_range(0..=u32::MAX).ui(ui);
+
+        if ui.button("Reroll").clicked() {
+            *seed = new_seed();
+        }
+    });
+}
+
+/// The most nodes the random network generators will make
+pub const MAX_GENERATED_NODES: u32 = 5000;
+/// The most edges the random network generators will make, any more can't be drawn smoothly
+pub const MAX_GENERATED_EDGES: u32 = 100_000;
+
+/// Spreads `count` nodes out evenly around a circle, returning where the `i`th one goes
+pub fn circle_layout(i: usize, count: usize) -> (f32, f32) {
+    let angle = std::f32::consts::TAU * i as f32 / count as f32;
+    (500.0 + 450.0 * angle.cos(), 500.0 + 450.0 * angle.sin())
+}
+
+/// Scales and moves a layout so it fills the space nodes are usually put in without
+/// stretching it
+pub fn fit_layout(positions: &mut [(f32, f32)]) {
+    let (mut min, mut max) = (
+        (f32::INFINITY, f32::INFINITY),
+        (f32::NEG_INFINITY, f32::NEG_INFINITY),
+    );
+    for (x, y) in positions.iter() {
+        min = (min.0.min(*x), min.1.min(*y));
+        max = (max.0.max(*x), max.1.max(*y));
+    }
+
+    let size = (max.0 - min.0).max(max.1 - min.1);
+    let scale = if size > 0.0 { 900.0 / size } else { 0.0 };
+    let center = ((min.0 + max.0) / 2.0, (min.1 + max.1) / 2.0);
+
+    for (x, y) in positions.iter_mut() {
+        *x = 500.0 + (*x - center.0) * scale;
+        *y = 500.0 + (*y - center.1) * scale;
+    }
+}
+
+/// The nodes and edges of a graph that's been worked out but not built yet
+pub struct GraphLayout {
+    /// Each node's position and name
+    pub nodes: Vec<((f32, f32), String)>,
+    /// Each edge as the positions of its nodes in `nodes`
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl GraphLayout {
+    /// Names each node by where it is in the list
+    pub fn numbered(positions: Vec<(f32, f32)>, edges: Vec<(usize, usize)>) -> Self {
+        let nodes = positions
+            .into_iter()
+            .enumerate()
+            .map(|(i, pos)| (pos, i.to_string()))
+            .collect();
+
+        GraphLayout { nodes, edges }
+    }
+
+    pub fn build(self, name: String, directed: bool) -> Graph {
+        let mut graph = Graph::new(name, directed, false);
+
+        let ids = self
+            .nodes
+            .into_iter()
+            .map(|(pos, name)| graph.add_node(pos, name, Vec::new()))
+            .collect::<Vec<_>>();
+
+        for (a, b) in self.edges {
+            graph.add_edge(ids[a], ids[b], None);
+        }
+
+        graph
+    }
+}
+
+/// Whether a generator gives its edges random weights, and the range they're picked from
+pub struct RandomWeights {
+    pub enabled: bool,
+    pub lower_bound: f32,
+    pub upper_bound: f32,
+}
+
+impl RandomWeights {
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.checkbox(&mut self.enabled, "Weighted Graph");
+
+        if self.enabled {
+            ui.horizontal(|ui| {
+                ui.label("Weights From");
+                DragValue::new(&mut self.lower_bound).ui(ui);
+
+                if self.upper_bound < self.lower_bound {
+                    self.upper_bound = self.lower_bound;
+                }
+
+                ui.label("To");
+                DragValue::new(&mut self.upper_bound)
+                    .clamp_range(self.lower_bound..=f32::INFINITY)
+                    .ui(ui);
+            });
+        }
+    }
+
+    /// Picks the weight for an edge, if there should be one
+    pub fn sample(&self, rng: &mut impl Rng) -> Option<f32> {
+        self.enabled
+            .then(|| rng.gen_range(self.lower_bound..=self.upper_bound))
+    }
+}
+
+impl Default for RandomWeights {
+    fn default() -> Self {
+        RandomWeights {
+            enabled: false,
+            lower_bound: 1.0,
+            upper_bound: 5.0,
+        }
+    }
+}
+
 pub trait GraphGenerator {
     fn name(&self) -> &'static str;
-    fn gen_graph(&mut self) -> Graph;
+    /// Makes a new graph, or explains why one couldn't be made
+    fn gen_graph(&mut self) -> Result<Graph, String>;
     fn ui(&mut self, ui: &mut Ui);
 }
 
@@ -31,10 +205,10 @@ impl GraphGenerator for EmptyGraphGenerator {
         "Empty Graph"
     }
 
-    fn gen_graph(&mut self) -> Graph {
+    fn gen_graph(&mut self) -> Result<Graph, String> {
         let graph = Graph::new(self.name.take(), self.directed, self.weighted);
         *self = Self::default();
-        graph
+        Ok(graph)
     }
 
     fn ui(&mut self, ui: &mut Ui) {
@@ -0,0 +1,151 @@
+use egui::{ComboBox, Ui};
+
+use crate::{
+    generation::{circle_layout, fit_layout, GraphGenerator, GraphLayout},
+    graph::Graph,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum NamedGraph {
+    Petersen,
+    Franklin,
+    Frucht,
+    Heawood,
+    MobiusKantor,
+    Pappus,
+    Desargues,
+    Dodecahedron,
+}
+
+impl NamedGraph {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            NamedGraph::Petersen => "Petersen",
+            NamedGraph::Franklin => "Franklin",
+            NamedGraph::Frucht => "Frucht",
+            NamedGraph::Heawood => "Heawood",
+            NamedGraph::MobiusKantor => "Möbius–Kantor",
+            NamedGraph::Pappus => "Pappus",
+            NamedGraph::Desargues => "Desargues",
+            NamedGraph::Dodecahedron => "Dodecahedron",
+        }
+    }
+
+    pub const fn values() -> [NamedGraph; 8] {
+        [
+            NamedGraph::Petersen,
+            NamedGraph::Franklin,
+            NamedGraph::Frucht,
+            NamedGraph::Heawood,
+            NamedGraph::MobiusKantor,
+            NamedGraph::Pappus,
+            NamedGraph::Desargues,
+            NamedGraph::Dodecahedron,
+        ]
+    }
+
+    /// The graph's node count and LCF notation, for the graphs that have a cycle through every
+    /// node
+    ///
+    /// The nodes go around that cycle, and the notation repeats to say how far along it each
+    /// node's other edge goes.
+    const fn lcf(&self) -> Option<(usize, &'static [isize])> {
+        Some(match self {
+            NamedGraph::Petersen => return None,
+            NamedGraph::Franklin => (12, &[5, -5]),
+            NamedGraph::Frucht => (12, &[-5, -2, -4, 2, 5, -2, 2, 5, -2, -5, 4, 2]),
+            NamedGraph::Heawood => (14, &[5, -5]),
+            NamedGraph::MobiusKantor => (16, &[5, -5]),
+            NamedGraph::Pappus => (18, &[5, 7, -7, 7, -7, -5]),
+            NamedGraph::Desargues => (20, &[5, -5, 9, -9]),
+            NamedGraph::Dodecahedron => (20, &[10, 7, 4, -4, -7, 10, -4, 7, -7, 4]),
+        })
+    }
+}
+
+/// Lays out a graph given in LCF notation around a circle
+fn from_lcf(count: usize, jumps: &[isize]) -> GraphLayout {
+    let positions = (0..count).map(|i| circle_layout(i, count)).collect();
+
+    let mut edges = (0..count).map(|i| (i, (i + 1) % count)).collect::<Vec<_>>();
+    for i in 0..count {
+        let j = (i as isize + jumps[i % jumps.len()]).rem_euclid(count as isize) as usize;
+
+        // Both ends of each of these edges list it, so it's only added from the first
+        if i < j {
+            edges.push((i, j));
+        }
+    }
+
+    GraphLayout::numbered(positions, edges)
+}
+
+/// Lays out the Petersen graph as a pentagon around a pentagram
+fn petersen() -> GraphLayout {
+    let point = |i: usize, radius: f32| {
+        let angle = std::f32::consts::TAU * i as f32 / 5.0 - std::f32::consts::FRAC_PI_2;
+        (radius * angle.cos(), radius * angle.sin())
+    };
+
+    let mut positions = (0..5).map(|i| point(i, 2.0)).collect::<Vec<_>>();
+    positions.extend((0..5).map(|i| point(i, 1.0)));
+    fit_layout(&mut positions);
+
+    let edges = (0..5)
+        .flat_map(|i| [(i, (i + 1) % 5), (i, i + 5), (i + 5, (i + 2) % 5 + 5)])
+        .collect();
+
+    GraphLayout::numbered(positions, edges)
+}
+
+/// Makes one of the well known graphs that have a name of their own
+pub struct NamedGraphGenerator {
+    graph_name: String,
+    graph: NamedGraph,
+}
+
+impl GraphGenerator for NamedGraphGenerator {
+    fn name(&self) -> &'static str {
+        "Named Graph"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        let graphs = NamedGraph::values();
+        let mut curr_graph = graphs.iter().position(|g| *g == self.graph).unwrap();
+
+        ComboBox::from_label("Graph").show_index(ui, &mut curr_graph, graphs.len(), |i| {
+            graphs[i].name().to_owned()
+        });
+
+        self.graph = graphs[curr_graph];
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let layout = match self.graph.lcf() {
+            Some((count, jumps)) => from_lcf(count, jumps),
+            None => petersen(),
+        };
+
+        let name = if self.graph_name.is_empty() {
+            format!("{} Graph", self.graph.name())
+        } else {
+            self.graph_name.clone()
+        };
+
+        Ok(layout.build(name, false))
+    }
+}
+
+impl Default for NamedGraphGenerator {
+    fn default() -> Self {
+        NamedGraphGenerator {
+            graph_name: String::new(),
+            graph: NamedGraph::Petersen,
+        }
+    }
+}
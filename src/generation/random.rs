@@ -2,9 +2,9 @@ use egui::{DragValue, Ui, Widget};
 use rand::Rng;
 
 use crate::{
-    generation::GraphGenerator,
-    graph::{Graph, NodeIndex},
-    traversers::{GraphTraversers, TraversalManager},
+    generation::{new_seed, seed_ui, seeded_rng, GraphGenerator},
+    graph::Graph,
+    traversers::connected_nodes,
 };
 
 pub struct RandomGraphMenu {
@@ -16,6 +16,7 @@ pub struct RandomGraphMenu {
     weights: bool,
     weight_lower_bound: f32,
     weight_upper_bound: f32,
+    seed: u64,
 }
 
 impl GraphGenerator for RandomGraphMenu {
@@ -29,6 +30,8 @@ impl GraphGenerator for RandomGraphMenu {
             ui.text_edit_singleline(&mut self.graph_name);
         });
 
+        seed_ui(ui, &mut self.seed);
+
         ui.horizontal(|ui| {
             ui.label("Node Count");
             DragValue::new(&mut self.node_count)
@@ -66,18 +69,21 @@ impl GraphGenerator for RandomGraphMenu {
         }
     }
 
-    fn gen_graph(&mut self) -> Graph {
+    fn gen_graph(&mut self) -> Result<Graph, String> {
         let edge_count = self.edge_count;
         let node_count = self.node_count;
 
         let mut graph = Graph::new(self.graph_name.clone(), self.directed, self.weights);
-        let mut rng = rand::thread_rng();
+        let mut rng = seeded_rng(self.seed);
+        graph.set_seed(Some(self.seed));
 
-        for i in 0..node_count {
-            let x = rng.gen_range(5.0..995.0);
-            let y = rng.gen_range(5.0..995.0);
-            graph.add_node((x, y), i.to_string(), Vec::new());
-        }
+        let ids = (0..node_count)
+            .map(|i| {
+                let x = rng.gen_range(5.0..995.0);
+                let y = rng.gen_range(5.0..995.0);
+                graph.add_node((x, y), i.to_string(), Vec::new())
+            })
+            .collect::<Vec<_>>();
 
         for i in 0..edge_count {
             // If we are making a connected graph, we ensure that each node gets at least 1 edge
@@ -93,8 +99,8 @@ impl GraphGenerator for RandomGraphMenu {
                 b = rng.gen_range(0..node_count) as usize;
             }
 
-            let a = NodeIndex(a);
-            let mut b = NodeIndex(b);
+            let a = ids[a];
+            let mut b = ids[b];
 
             while graph.get_node(a).get_edges().iter().any(|e| {
                 let (c, d) = e.get_nodes();
@@ -106,7 +112,7 @@ impl GraphGenerator for RandomGraphMenu {
                     false
                 }
             }) {
-                b = NodeIndex(rng.gen_range(0..node_count) as usize);
+                b = ids[rng.gen_range(0..node_count) as usize];
             }
 
             let weight = if self.weights {
@@ -119,88 +125,58 @@ impl GraphGenerator for RandomGraphMenu {
         }
 
         if self.connected {
-            'connection_test: loop {
-                let mut manager = TraversalManager::new(GraphTraversers::SimpleBreadth);
+            loop {
+                let connected = connected_nodes(&graph, ids[0]);
 
-                manager.new_traversal(NodeIndex(0), NodeIndex(self.node_count as usize - 1));
-
-                while manager.currently_traversing {
-                    manager.update(&mut graph)
+                if connected.len() == self.node_count as usize {
+                    break;
                 }
 
                 let mut edges_to_add = Vec::new();
 
-                if let Some(traversal) = manager.traversal {
-                    if traversal.visited.len() != self.node_count as usize {
-                        for node in graph.get_nodes_mut() {
-                            if !traversal.visited.contains(&node.get_id()) {
-                                let id = node.get_id();
-
-                                let mut b = rng.gen_range(0..node_count) as usize;
-
-                                while id.0 == b && node_count > 1 {
-                                    b = rng.gen_range(0..node_count) as usize;
-                                }
-
-                                let mut b = NodeIndex(b);
-
-                                while node.get_edges().iter().any(|e| {
-                                    let (c, d) = e.get_nodes();
-                                    if c == id {
-                                        d == b
-                                    } else if !self.directed {
-                                        c == b
-                                    } else {
-                                        false
-                                    }
-                                }) {
-                                    b = NodeIndex(rng.gen_range(0..node_count) as usize);
-                                }
-
-                                let weight = if self.weights {
-                                    Some(rng.gen_range(
-                                        self.weight_lower_bound..self.weight_upper_bound,
-                                    ))
-                                } else {
-                                    None
-                                };
-
-                                edges_to_add.push((id, b, weight));
-                            }
-                        }
+                for node in graph.get_nodes() {
+                    if !connected.contains(&node.get_id()) {
+                        let id = node.get_id();
 
-                        graph.reset();
+                        let mut b = rng.gen_range(0..node_count) as usize;
 
-                        for (a, b, weight) in edges_to_add.into_iter() {
-                            graph.add_edge(a, b, weight);
+                        while id == ids[b] && node_count > 1 {
+                            b = rng.gen_range(0..node_count) as usize;
+                        }
 
-                            let mut manager = TraversalManager::new(GraphTraversers::SimpleBreadth);
-                            manager.new_traversal(
-                                NodeIndex(0),
-                                NodeIndex(self.node_count as usize - 1),
-                            );
+                        let mut b = ids[b];
 
-                            while manager.currently_traversing {
-                                manager.update(&mut graph)
+                        while node.get_edges().iter().any(|e| {
+                            let (c, d) = e.get_nodes();
+                            if c == id {
+                                d == b
+                            } else if !self.directed {
+                                c == b
+                            } else {
+                                false
                             }
+                        }) {
+                            b = ids[rng.gen_range(0..node_count) as usize];
+                        }
 
-                            if traversal.visited.len() == self.node_count as usize {
-                                break 'connection_test;
-                            }
+                        let weight = if self.weights {
+                            Some(rng.gen_range(self.weight_lower_bound..self.weight_upper_bound))
+                        } else {
+                            None
+                        };
 
-                            graph.reset();
-                        }
-                    } else {
-                        break;
+                        edges_to_add.push((id, b, weight));
                     }
                 }
+
+                for (a, b, weight) in edges_to_add {
+                    graph.add_edge(a, b, weight);
+                }
             }
         }
 
-        *self = Self::default();
-
         graph.reset();
-        graph
+        Ok(graph)
     }
 }
 
@@ -215,6 +191,7 @@ impl Default for RandomGraphMenu {
             weights: false,
             weight_lower_bound: 1.0,
             weight_upper_bound: 5.0,
+            seed: new_seed(),
         }
     }
 }
@@ -0,0 +1,315 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use egui::{ComboBox, DragValue, Ui, Widget};
+use rand::Rng;
+
+use crate::{
+    generation::{
+        new_seed, seed_ui, seeded_rng, GraphGenerator, RandomWeights, MAX_GENERATED_NODES,
+    },
+    graph::Graph,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TreeKind {
+    /// Every tree on the nodes is as likely as any other, made by decoding a random Prüfer
+    /// sequence
+    Uniform,
+    /// Each node has at most a set number of children and the tree can't go past a set depth
+    KAry,
+}
+
+impl TreeKind {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            TreeKind::Uniform => "Uniform Labelled Tree",
+            TreeKind::KAry => "K-ary Tree",
+        }
+    }
+
+    pub const fn values() -> [TreeKind; 2] {
+        [TreeKind::Uniform, TreeKind::KAry]
+    }
+}
+
+/// Lays a tree out from its root at the top down to its leaves at the bottom, with the leaves
+/// spread out evenly and each parent centered over its children
+pub fn tree_layout(children: &[Vec<usize>], root: usize) -> Vec<(f32, f32)> {
+    let mut depths = vec![0; children.len()];
+    let mut preorder = Vec::with_capacity(children.len());
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        preorder.push(node);
+        for child in children[node].iter().rev() {
+            depths[*child] = depths[node] + 1;
+            stack.push(*child);
+        }
+    }
+
+    // Going through the leaves in preorder goes through them from left to right
+    let mut xs = vec![0.0; children.len()];
+    let mut slots = 0;
+    for node in &preorder {
+        if children[*node].is_empty() {
+            xs[*node] = slots as f32;
+            slots += 1;
+        }
+    }
+
+    // Every child comes after its parent in preorder, so this places children first
+    for node in preorder.iter().rev() {
+        if let (Some(first), Some(last)) = (children[*node].first(), children[*node].last()) {
+            xs[*node] = (xs[*first] + xs[*last]) / 2.0;
+        }
+    }
+
+    let width = (slots - 1).max(1) as f32;
+    let height = depths.iter().copied().max().unwrap_or(0).max(1) as f32;
+
+    xs.into_iter()
+        .zip(depths)
+        .map(|(x, depth)| {
+            let x = if slots > 1 {
+                50.0 + 900.0 * x / width
+            } else {
+                500.0
+            };
+            (x, 50.0 + 900.0 * depth as f32 / height)
+        })
+        .collect()
+}
+
+/// Finds each node's children and parent in a tree given by its edges when it's hung from
+/// `root`
+fn hang_from(edges: &[(usize, usize)], root: usize) -> (Vec<Vec<usize>>, Vec<Option<usize>>) {
+    let count = edges.len() + 1;
+
+    let mut adjacent = vec![Vec::new(); count];
+    for (a, b) in edges {
+        adjacent[*a].push(*b);
+        adjacent[*b].push(*a);
+    }
+
+    let mut children = vec![Vec::new(); count];
+    let mut parents = vec![None; count];
+    let mut stack = vec![root];
+
+    while let Some(node) = stack.pop() {
+        for next in &adjacent[node] {
+            if *next != root && parents[*next].is_none() {
+                parents[*next] = Some(node);
+                children[node].push(*next);
+                stack.push(*next);
+            }
+        }
+    }
+
+    (children, parents)
+}
+
+/// Makes a random tree, laid out from its root down
+///
+/// In directed trees every edge goes from a parent to its child.
+pub struct RandomTreeGenerator {
+    graph_name: String,
+    kind: TreeKind,
+    node_count: u32,
+    /// The most children a node in a k-ary tree can have
+    max_children: u32,
+    /// How many edges a k-ary tree can go down from the root
+    depth_limit: u32,
+    directed: bool,
+    weights: RandomWeights,
+    seed: u64,
+}
+
+impl RandomTreeGenerator {
+    /// How many nodes a k-ary tree can fit with every node filled up to the depth limit
+    fn capacity(&self) -> u32 {
+        let mut capacity = 1u32;
+        let mut level = 1u32;
+
+        for _ in 0..self.depth_limit {
+            level = level.saturating_mul(self.max_children);
+            capacity = capacity.saturating_add(level);
+        }
+
+        capacity
+    }
+
+    /// Decodes a random Prüfer sequence into the tree's edges
+    fn uniform_edges(&self, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+        let n = self.node_count as usize;
+        if n < 2 {
+            return Vec::new();
+        }
+
+        let sequence = (0..n - 2)
+            .map(|_| rng.gen_range(0..n as u32) as usize)
+            .collect::<Vec<_>>();
+
+        // A node's degree is one more than the number of times it's in the sequence
+        let mut degrees = vec![1; n];
+        for node in &sequence {
+            degrees[*node] += 1;
+        }
+
+        let mut leaves = (0..n)
+            .filter(|node| degrees[*node] == 1)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+
+        let mut edges = Vec::with_capacity(n - 1);
+        for node in sequence {
+            let Some(Reverse(leaf)) = leaves.pop() else {
+                break;
+            };
+            edges.push((node, leaf));
+
+            degrees[node] -= 1;
+            if degrees[node] == 1 {
+                leaves.push(Reverse(node));
+            }
+        }
+
+        // That leaves two nodes to be joined together
+        if let (Some(Reverse(a)), Some(Reverse(b))) = (leaves.pop(), leaves.pop()) {
+            edges.push((a, b));
+        }
+
+        edges
+    }
+
+    /// Adds each node under a random node that still has room for it
+    fn k_ary_edges(&self, rng: &mut impl Rng) -> Vec<(usize, usize)> {
+        let n = self.node_count.min(self.capacity()) as usize;
+        let k = self.max_children as usize;
+
+        let mut children = vec![0; n];
+        let mut depths = vec![0; n];
+        // The nodes that could still take another child
+        let mut open = if self.depth_limit > 0 {
+            vec![0]
+        } else {
+            Vec::new()
+        };
+        let mut edges = Vec::with_capacity(n.saturating_sub(1));
+
+        for node in 1..n {
+            let i = rng.gen_range(0..open.len() as u32) as usize;
+            let parent = open[i];
+
+            edges.push((parent, node));
+            children[parent] += 1;
+            depths[node] = depths[parent] + 1;
+
+            if children[parent] == k {
+                open.swap_remove(i);
+            }
+            if depths[node] < self.depth_limit {
+                open.push(node);
+            }
+        }
+
+        edges
+    }
+}
+
+impl GraphGenerator for RandomTreeGenerator {
+    fn name(&self) -> &'static str {
+        "Random Tree"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        seed_ui(ui, &mut self.seed);
+
+        let kinds = TreeKind::values();
+        let mut curr_kind = kinds.iter().position(|k| *k == self.kind).unwrap();
+
+        ComboBox::from_label("Tree Kind").show_index(ui, &mut curr_kind, kinds.len(), |i| {
+            kinds[i].name().to_owned()
+        });
+
+        self.kind = kinds[curr_kind];
+
+        let mut max_nodes = MAX_GENERATED_NODES;
+
+        if self.kind == TreeKind::KAry {
+            ui.horizontal(|ui| {
+                ui.label("Most Children");
+                DragValue::new(&mut self.max_children)
+                    .clamp_range(1..=8)
+                    .ui(ui);
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Depth Limit");
+                DragValue::new(&mut self.depth_limit)
+                    .clamp_range(0..=20)
+                    .ui(ui);
+            });
+
+            max_nodes = max_nodes.min(self.capacity());
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Node Count");
+            DragValue::new(&mut self.node_count)
+                .clamp_range(1..=max_nodes)
+                .ui(ui);
+        });
+
+        ui.checkbox(&mut self.directed, "Directed Graph");
+        self.weights.ui(ui);
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let mut rng = seeded_rng(self.seed);
+
+        let edges = match self.kind {
+            TreeKind::Uniform => self.uniform_edges(&mut rng),
+            TreeKind::KAry => self.k_ary_edges(&mut rng),
+        };
+        // Uniform trees don't have a root, so they hang from the first node
+        let (children, parents) = hang_from(&edges, 0);
+
+        let mut graph = Graph::new(self.graph_name.clone(), self.directed, self.weights.enabled);
+        graph.set_seed(Some(self.seed));
+
+        let ids = tree_layout(&children, 0)
+            .into_iter()
+            .enumerate()
+            .map(|(i, pos)| graph.add_node(pos, i.to_string(), Vec::new()))
+            .collect::<Vec<_>>();
+
+        for (child, parent) in parents.into_iter().enumerate() {
+            if let Some(parent) = parent {
+                graph.add_edge(ids[parent], ids[child], self.weights.sample(&mut rng));
+            }
+        }
+
+        graph.reset();
+        Ok(graph)
+    }
+}
+
+impl Default for RandomTreeGenerator {
+    fn default() -> Self {
+        RandomTreeGenerator {
+            graph_name: String::new(),
+            kind: TreeKind::Uniform,
+            node_count: 15,
+            max_children: 2,
+            depth_limit: 4,
+            directed: false,
+            weights: RandomWeights::default(),
+            seed: new_seed(),
+        }
+    }
+}
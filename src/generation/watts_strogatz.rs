@@ -0,0 +1,134 @@
+use std::collections::HashSet;
+
+use egui::{DragValue, Ui, Widget};
+use rand::Rng;
+
+use crate::{
+    generation::{
+        circle_layout, new_seed, seed_ui, seeded_rng, GraphGenerator, RandomWeights,
+        MAX_GENERATED_NODES,
+    },
+    graph::Graph,
+};
+
+/// Makes a small-world graph, where most edges are between nearby nodes but a few shortcuts
+/// make every node only a few edges from any other
+///
+/// The nodes start off in a ring with each joined to its nearest neighbors on both sides, then
+/// each edge has a chance of having its far end moved to a random node.
+pub struct WattsStrogatzGenerator {
+    graph_name: String,
+    node_count: u32,
+    /// How many nodes around the ring each node starts off joined to, half on each side
+    neighbors: u32,
+    rewire_probability: f64,
+    weights: RandomWeights,
+    seed: u64,
+}
+
+impl GraphGenerator for WattsStrogatzGenerator {
+    fn name(&self) -> &'static str {
+        "Watts–Strogatz Random Graph"
+    }
+
+    fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Graph Name");
+            ui.text_edit_singleline(&mut self.graph_name);
+        });
+
+        seed_ui(ui, &mut self.seed);
+
+        ui.horizontal(|ui| {
+            ui.label("Node Count");
+            DragValue::new(&mut self.node_count)
+                .clamp_range(3..=MAX_GENERATED_NODES)
+                .ui(ui);
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Neighbors");
+            DragValue::new(&mut self.neighbors)
+                .clamp_range(2..=(self.node_count - 1).min(20))
+                .speed(2.0)
+                .ui(ui);
+        });
+        // The neighbors are split evenly between both sides
+        self.neighbors -= self.neighbors % 2;
+
+        ui.horizontal(|ui| {
+            ui.label("Rewiring Probability");
+            DragValue::new(&mut self.rewire_probability)
+                .clamp_range(0.0..=1.0)
+                .speed(0.01)
+                .ui(ui);
+        });
+
+        self.weights.ui(ui);
+    }
+
+    fn gen_graph(&mut self) -> Result<Graph, String> {
+        let mut rng = seeded_rng(self.seed);
+
+        let count = self.node_count as usize;
+        // Keeping this under half the ring means no two nodes get joined from both sides
+        let half = (self.neighbors as usize / 2).min((count - 1) / 2);
+
+        let mut edges = Vec::new();
+        let mut present = HashSet::new();
+        let mut degrees = vec![2 * half; count];
+
+        for offset in 1..=half {
+            for a in 0..count {
+                let b = (a + offset) % count;
+                edges.push((a, b));
+                present.insert((a.min(b), a.max(b)));
+            }
+        }
+
+        // Each edge keeps its near end and might have its far end moved
+        for (i, (a, b)) in edges.clone().into_iter().enumerate() {
+            if !rng.gen_bool(self.rewire_probability) || degrees[a] >= count - 1 {
+                continue;
+            }
+
+            let mut c = rng.gen_range(0..count as u32) as usize;
+            while c == a || present.contains(&(a.min(c), a.max(c))) {
+                c = rng.gen_range(0..count as u32) as usize;
+            }
+
+            present.remove(&(a.min(b), a.max(b)));
+            present.insert((a.min(c), a.max(c)));
+            degrees[b] -= 1;
+            degrees[c] += 1;
+            edges[i] = (a, c);
+        }
+
+        let mut graph = Graph::new(self.graph_name.clone(), false, self.weights.enabled);
+        graph.set_seed(Some(self.seed));
+
+        let ids = (0..count)
+            .map(|i| graph.add_node(circle_layout(i, count), i.to_string(), Vec::new()))
+            .collect::<Vec<_>>();
+
+        for (a, b) in edges {
+            graph.add_edge(ids[a], ids[b], self.weights.sample(&mut rng));
+        }
+
+        graph.reset();
+        Ok(graph)
+    }
+}
+
+impl Default for WattsStrogatzGenerator {
+    fn default() -> Self {
+        WattsStrogatzGenerator {
+            graph_name: String::new(),
+            node_count: 30,
+            neighbors: 4,
+            rewire_probability: 0.1,
+            weights: RandomWeights::default(),
+            seed: new_seed(),
+        }
+    }
+}
@@ -1,24 +1,104 @@
-use std::hash::Hash;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    hash::Hash,
+};
 
+use serde::{Deserialize, Serialize};
+
+use crate::history::{EditHistory, GraphEdit};
+
+/// A handle to a node in a [`Graph`].
+///
+/// Handles are generational, so once a node is removed any handle to it stops resolving
+/// instead of pointing at whatever node ends up reusing its slot.
 #[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
-pub struct NodeIndex(pub usize);
+pub struct NodeIndex {
+    slot: u32,
+    generation: u32,
+}
 
-impl NodeIndex {
-    pub fn index(&self) -> usize {
-        self.0
+/// An entry in the graph's slot table.
+///
+/// `position` is where the node currently lives in `Graph::nodes`, or `None` if the slot is free.
+/// `newest` is the highest generation this slot has used, so a node that gets restored by undo
+/// can take back its old generation without a later node ever reusing it.
+struct Slot {
+    generation: u32,
+    newest: u32,
+    position: Option<usize>,
+}
+
+/// Everything needed to put a removed node back exactly how it was
+pub struct RemovedNode {
+    node: Node,
+    position: usize,
+    /// Edges stored on other nodes that went to the removed node
+    incoming: Vec<Edge>,
+    edge_attributes: Vec<(EdgeId, Attributes)>,
+}
+
+impl RemovedNode {
+    pub fn node(&self) -> &Node {
+        &self.node
     }
+}
+
+/// Extra data attached to a graph, node, or edge that we don't use ourselves
+///
+/// These come from imported files and are kept around so they can be written back out.
+pub type Attributes = BTreeMap<String, AttributeValue>;
+
+/// Tells edges apart so parallel edges can have their own attributes.
+/// Both halves of an undirected edge share one.
+pub type EdgeId = u32;
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    List(Vec<(String, AttributeValue)>),
+}
 
-    pub fn next(&self) -> Self {
-        NodeIndex(self.0 + 1)
+impl Display for AttributeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AttributeValue::Bool(b) => write!(f, "{b}"),
+            AttributeValue::Int(i) => write!(f, "{i}"),
+            AttributeValue::Float(x) => write!(f, "{x}"),
+            AttributeValue::String(s) => write!(f, "{s}"),
+            AttributeValue::List(items) => {
+                write!(f, "[")?;
+                for (i, (key, value)) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{key} {value}")?;
+                }
+                write!(f, "]")
+            }
+        }
     }
 }
 
 pub struct Graph {
     name: String,
     nodes: Vec<Node>,
-    next_index: NodeIndex,
+    slots: Vec<Slot>,
+    free_slots: Vec<u32>,
     is_directed: bool,
     is_weighted: bool,
+    attributes: Attributes,
+    /// Edges are copied around a lot so their attributes live here instead of on the edge
+    edge_attributes: HashMap<EdgeId, Attributes>,
+    /// The id the next edge added gets
+    next_edge_id: EdgeId,
+    history: EditHistory,
+    /// The seed a random graph was generated from, so it can be made again
+    seed: Option<u64>,
 }
 
 impl Graph {
@@ -26,9 +106,15 @@ impl Graph {
         Graph {
             name,
             nodes: Vec::new(),
-            next_index: NodeIndex(0),
+            slots: Vec::new(),
+            free_slots: Vec::new(),
             is_directed: directed,
             is_weighted: weighted,
+            attributes: Attributes::new(),
+            edge_attributes: HashMap::new(),
+            next_edge_id: 0,
+            history: EditHistory::default(),
+            seed: None,
         }
     }
 
@@ -37,50 +123,341 @@ impl Graph {
         pos: (f32, f32),
         name: String,
         connections: Vec<(NodeIndex, Option<f32>)>,
-    ) {
-        for (idx, weight) in &connections {
-            let n = self.nodes.get_mut(idx.index()).unwrap();
-            n.add_edge(self.next_index, *weight)
+    ) -> NodeIndex {
+        let id = self.allocate_slot();
+
+        self.slots[id.slot as usize].position = Some(self.nodes.len());
+        self.nodes.push(Node::new(id, pos, name));
+
+        for (idx, weight) in connections {
+            let edge_id = self.new_edge_id();
+            self.get_node_mut(idx).add_edge(id, weight, edge_id);
+            self.get_node_mut(id).add_edge(idx, weight, edge_id);
         }
 
-        self.nodes
-            .push(Node::new(self.next_index, pos, name, connections));
+        id
+    }
+
+    /// Removes a node and every edge going to or from it.
+    ///
+    /// Returns `None` if `idx` doesn't refer to a node in this graph.
+    /// Otherwise the removed node can be put back with [`Graph::restore_node`].
+    pub fn remove_node(&mut self, idx: NodeIndex) -> Option<RemovedNode> {
+        let position = self.position(idx)?;
+
+        let mut incoming = Vec::new();
+        for node in &mut self.nodes {
+            if node.id != idx {
+                incoming.extend(node.edges.iter().filter(|e| e.get_nodes().1 == idx));
+                node.remove_edge(idx);
+            }
+        }
+
+        let edge_attributes = self.nodes[position]
+            .edges
+            .iter()
+            .chain(&incoming)
+            .filter_map(|e| Some((e.6, self.edge_attributes.remove(&e.6)?)))
+            .collect();
+
+        let node = self.nodes.remove(position);
+
+        // Every node after the removed one has shifted down by one
+        for moved in &self.nodes[position..] {
+            if let Some(p) = &mut self.slots[moved.id.slot as usize].position {
+                *p -= 1;
+            }
+        }
+
+        let slot = &mut self.slots[idx.slot as usize];
+        slot.position = None;
+        // Bumping the generation invalidates every handle to the removed node. Once a slot has
+        // used every generation it's retired, since wrapping round would bring old handles back.
+        if let Some(newest) = slot.newest.checked_add(1) {
+            slot.newest = newest;
+            slot.generation = newest;
+            self.free_slots.push(idx.slot);
+        }
 
-        self.next_index = self.next_index.next();
+        Some(RemovedNode {
+            node,
+            position,
+            incoming,
+            edge_attributes,
+        })
+    }
+
+    /// Puts back a node removed by [`Graph::remove_node`], keeping its old [`NodeIndex`]
+    ///
+    /// Edges to nodes that have since been removed aren't restored. If another node has taken
+    /// its slot, which can only happen if edits are undone out of order, the node is given back.
+    pub fn restore_node(&mut self, removed: RemovedNode) -> Result<(), Box<RemovedNode>> {
+        if self.slots[removed.node.id.slot as usize].position.is_some() {
+            return Err(Box::new(removed));
+        }
+
+        let RemovedNode {
+            mut node,
+            position,
+            incoming,
+            edge_attributes,
+        } = removed;
+        let idx = node.id;
+
+        let slot = &mut self.slots[idx.slot as usize];
+        slot.generation = idx.generation;
+        self.free_slots.retain(|s| *s != idx.slot);
+
+        node.edges.retain(|e| self.contains_node(e.get_nodes().1));
+        let mut restored = node.edges.iter().map(|e| e.6).collect::<Vec<_>>();
+
+        let position = position.min(self.nodes.len());
+        self.nodes.insert(position, node);
+        for (i, moved) in self.nodes.iter().enumerate().skip(position) {
+            self.slots[moved.id.slot as usize].position = Some(i);
+        }
+
+        for edge in incoming {
+            if let Some(node) = self.try_get_node_mut(edge.get_nodes().0) {
+                node.edges.push(edge);
+                restored.push(edge.6);
+            }
+        }
+
+        for (id, attributes) in edge_attributes {
+            if restored.contains(&id) {
+                self.edge_attributes.insert(id, attributes);
+            }
+        }
+
+        Ok(())
     }
 
     pub fn add_edge(&mut self, a: NodeIndex, b: NodeIndex, weight: Option<f32>) {
+        self.add_edge_with_attributes(a, b, weight, Attributes::new());
+    }
+
+    pub fn add_edge_with_attributes(
+        &mut self,
+        a: NodeIndex,
+        b: NodeIndex,
+        weight: Option<f32>,
+        attributes: Attributes,
+    ) {
         if a == b {
             return;
         }
 
-        self.nodes[a.index()].add_edge(b, weight);
+        let mut edge = Edge::new(a, b, weight);
+        edge.6 = self.new_edge_id();
+        self.restore_edge(edge, attributes);
+    }
+
+    /// Puts back an edge that was removed, keeping its id so it gets its own attributes back
+    pub fn restore_edge(&mut self, edge: Edge, attributes: Attributes) {
+        let (weight, a, b) = edge.get_weighted_nodes();
+
+        self.get_node_mut(a).add_edge(b, Some(weight), edge.6);
         if !self.is_directed {
-            self.nodes[b.index()].add_edge(a, weight);
+            self.get_node_mut(b).add_edge(a, Some(weight), edge.6);
+        }
+
+        if !attributes.is_empty() {
+            self.edge_attributes.insert(edge.6, attributes);
         }
     }
 
+    fn new_edge_id(&mut self) -> EdgeId {
+        let id = self.next_edge_id;
+        self.next_edge_id += 1;
+        id
+    }
+
+    /// Removes every edge from `a` to `b` along with their attributes
     pub fn remove_edge(&mut self, e: Edge) {
         let (a, b) = e.get_nodes();
-        self.nodes[a.index()].remove_edge(b);
+
+        let node = self.get_node_mut(a);
+        let ids = node
+            .edges
+            .iter()
+            .filter(|e| e.2 == b)
+            .map(|e| e.6)
+            .collect::<Vec<_>>();
+        node.remove_edge(b);
+
+        for id in ids {
+            self.edge_attributes.remove(&id);
+        }
+
+        if !self.is_directed {
+            self.get_node_mut(b).remove_edge(a);
+        }
+    }
+
+    /// Removes the edge from `a` to `b` that was added last, leaving any parallel edges alone
+    pub fn remove_last_edge(&mut self, a: NodeIndex, b: NodeIndex) {
+        if let Some(edge) = self.get_node_mut(a).remove_last_edge(b) {
+            self.edge_attributes.remove(&edge.6);
+        }
+
+        if !self.is_directed {
+            self.get_node_mut(b).remove_last_edge(a);
+        }
+    }
+
+    /// Changes the weight of the edge from `a` to `b` that weighs `old`, so parallel edges can
+    /// be told apart, returning false if there wasn't one
+    pub fn set_edge_weight(&mut self, a: NodeIndex, b: NodeIndex, old: f32, new: f32) -> bool {
+        let Some(changed) = self
+            .try_get_node_mut(a)
+            .map(|n| n.set_edge_weight(b, old, new))
+        else {
+            return false;
+        };
+
+        if !self.is_directed {
+            if let Some(node) = self.try_get_node_mut(b) {
+                node.set_edge_weight(a, old, new);
+            }
+        }
+
+        changed
+    }
+
+    /// Performs an edit and adds it to the graph's history so it can be undone
+    ///
+    /// If `merge` is set the edit gets combined with the last one when they change the same thing,
+    /// which stops every frame of a drag from becoming its own undo step.
+    pub fn edit(&mut self, mut edit: GraphEdit, merge: bool) {
+        edit.apply(self);
+        self.history.push(edit, merge);
+    }
+
+    /// Undoes the last edit, returning whether it changed which nodes or edges are in the graph
+    pub fn undo(&mut self) -> Option<bool> {
+        let mut edit = self.history.pop_undo()?;
+        let structural = edit.is_structural();
+        edit.revert(self);
+        self.history.push_redo(edit);
+        Some(structural)
+    }
+
+    /// Redoes the last undone edit, returning whether it changed which nodes or edges are in the graph
+    pub fn redo(&mut self) -> Option<bool> {
+        let mut edit = self.history.pop_redo()?;
+        let structural = edit.is_structural();
+        edit.apply(self);
+        self.history.push_undo(edit);
+        Some(structural)
+    }
+
+    pub fn history(&self) -> &EditHistory {
+        &self.history
+    }
+
+    /// Marks the edge from `a` to `b`, and its other half if we're undirected
+    pub fn set_edge_state(&mut self, a: NodeIndex, b: NodeIndex, state: EdgeState) {
+        if let Some(node) = self.try_get_node_mut(a) {
+            node.set_edge_state(b, state);
+        }
+
+        if !self.is_directed {
+            if let Some(node) = self.try_get_node_mut(b) {
+                node.set_edge_state(a, state);
+            }
+        }
+    }
+
+    /// Marks just one of the edges from `a` to `b` with this weight, and its other half if we're
+    /// undirected, so parallel edges can be told apart
+    ///
+    /// Accepted edges are never marked again, so rejecting one copy leaves another in the tree.
+    pub fn set_single_edge_state(
+        &mut self,
+        a: NodeIndex,
+        b: NodeIndex,
+        weight: f32,
+        state: EdgeState,
+    ) {
+        if let Some(node) = self.try_get_node_mut(a) {
+            node.set_single_edge_state(b, weight, state);
+        }
 
         if !self.is_directed {
-            self.nodes[b.index()].remove_edge(a);
+            if let Some(node) = self.try_get_node_mut(b) {
+                node.set_single_edge_state(a, weight, state);
+            }
         }
     }
 
+    /// Moves one of the edges from `a` to `b` marked `from` on to `to`, and its other half if
+    /// we're undirected, so parallel edges can be marked one at a time
+    pub fn change_edge_state(
+        &mut self,
+        a: NodeIndex,
+        b: NodeIndex,
+        from: EdgeState,
+        to: EdgeState,
+    ) {
+        if let Some(node) = self.try_get_node_mut(a) {
+            node.change_edge_state(b, from, to);
+        }
+
+        if !self.is_directed {
+            if let Some(node) = self.try_get_node_mut(b) {
+                node.change_edge_state(a, from, to);
+            }
+        }
+    }
+
+    /// Sets how much is flowing along the edges from `a` to `b`
+    pub fn set_edge_flow(&mut self, a: NodeIndex, b: NodeIndex, flow: Option<f32>) {
+        if let Some(node) = self.try_get_node_mut(a) {
+            node.set_edge_flow(b, flow);
+        }
+    }
+
+    /// Numbers the edges along a path in the order it takes them, taking the numbers off
+    /// every other edge
+    pub fn number_edges(&mut self, path: &[NodeIndex]) {
+        for node in &mut self.nodes {
+            for edge in &mut node.edges {
+                edge.5 = None;
+            }
+        }
+
+        for (i, pair) in path.windows(2).enumerate() {
+            if let Some(node) = self.try_get_node_mut(pair[0]) {
+                node.number_edge(pair[1], i + 1);
+            }
+
+            if !self.is_directed {
+                if let Some(node) = self.try_get_node_mut(pair[1]) {
+                    node.number_edge(pair[0], i + 1);
+                }
+            }
+        }
+    }
+
+    pub fn contains_node(&self, idx: NodeIndex) -> bool {
+        self.position(idx).is_some()
+    }
+
+    /// Gets the node `idx` refers to
+    ///
+    /// Panics if the node has been removed, use [`Graph::try_get_node`] if that is possible.
     pub fn get_node(&self, idx: NodeIndex) -> &Node {
-        // we assume the idx is always correct
-        // the newtype should ensure that the indices shold always come from valid places
-        self.nodes.get(idx.index()).unwrap()
+        self.try_get_node(idx)
+            .expect("NodeIndex should refer to a node in this graph")
     }
 
     pub fn try_get_node(&self, idx: NodeIndex) -> Option<&Node> {
-        self.nodes.get(idx.index())
+        self.position(idx).map(|p| &self.nodes[p])
     }
 
     pub fn try_get_node_mut(&mut self, idx: NodeIndex) -> Option<&mut Node> {
-        self.nodes.get_mut(idx.index())
+        self.position(idx).map(|p| &mut self.nodes[p])
     }
 
     pub fn get_nodes(&self) -> &[Node] {
@@ -91,8 +468,30 @@ impl Graph {
         self.nodes.as_mut_slice()
     }
 
+    /// Gets the node `idx` refers to
+    ///
+    /// Panics if the node has been removed, use [`Graph::try_get_node_mut`] if that is possible.
     pub fn get_node_mut(&mut self, idx: NodeIndex) -> &mut Node {
-        self.nodes.get_mut(idx.index()).unwrap()
+        self.try_get_node_mut(idx)
+            .expect("NodeIndex should refer to a node in this graph")
+    }
+
+    /// Gets every edge in the graph
+    ///
+    /// Undirected graphs store each edge on both of its nodes, so we only return the copy stored
+    /// on whichever node comes first.
+    pub fn get_edges(&self) -> Vec<Edge> {
+        self.nodes
+            .iter()
+            .enumerate()
+            .flat_map(|(i, node)| {
+                node.edges.iter().copied().filter(move |e| {
+                    let (a, b) = e.get_nodes();
+                    a == node.id
+                        && (self.is_directed || matches!(self.position(b), Some(p) if p > i))
+                })
+            })
+            .collect()
     }
 
     pub fn reset(&mut self) {
@@ -111,6 +510,58 @@ impl Graph {
         self.name.clone()
     }
 
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    pub fn attributes_mut(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
+
+    pub fn get_seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn set_seed(&mut self, seed: Option<u64>) {
+        self.seed = seed;
+    }
+
+    /// Gets the attributes of an edge, which both halves of an undirected edge share
+    pub fn edge_attributes(&self, edge: &Edge) -> Option<&Attributes> {
+        self.edge_attributes.get(&edge.6)
+    }
+
+    /// Finds the current position of `idx` in `nodes`, checking that it hasn't been removed
+    fn position(&self, idx: NodeIndex) -> Option<usize> {
+        let slot = self.slots.get(idx.slot as usize)?;
+
+        if slot.generation == idx.generation {
+            slot.position
+        } else {
+            None
+        }
+    }
+
+    fn allocate_slot(&mut self) -> NodeIndex {
+        if let Some(slot) = self.free_slots.pop() {
+            NodeIndex {
+                slot,
+                generation: self.slots[slot as usize].generation,
+            }
+        } else {
+            self.slots.push(Slot {
+                generation: 0,
+                newest: 0,
+                position: None,
+            });
+
+            NodeIndex {
+                slot: self.slots.len() as u32 - 1,
+                generation: 0,
+            }
+        }
+    }
+
     pub fn get_connections(&self, idx: NodeIndex) -> Vec<NodeIndex> {
         if self.is_directed {
             let mut in_bound: Vec<NodeIndex> = self
@@ -130,7 +581,7 @@ impl Graph {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq)]
 pub enum NodeState {
     None,
     Start,
@@ -139,6 +590,40 @@ pub enum NodeState {
     End,
 }
 
+/// How a traversal has marked an edge
+#[derive(Copy, Clone, PartialEq)]
+pub enum EdgeState {
+    None,
+    /// Relaxed during the current pass of an algorithm like Bellman-Ford
+    Relaxed,
+    /// Part of a cycle that stopped the traversal
+    Cycle,
+    /// Chosen to be part of a tree
+    Accepted,
+    /// Looked at and left out of a tree
+    Rejected,
+    /// Would disconnect the graph if it was removed
+    Bridge,
+    /// Part of a matching
+    Matched,
+    /// Joins two nodes that were given the same color
+    Conflict,
+}
+
+/// The parts of a node that a traversal changes, including the states of its edges
+#[derive(Clone, PartialEq)]
+pub struct NodeTraversal {
+    pub state: NodeState,
+    pub from_node: Option<NodeIndex>,
+    pub curr_path: Option<f32>,
+    pub badge: Option<String>,
+    pub group: Option<usize>,
+    pub ring: bool,
+    pub edge_states: Vec<EdgeState>,
+    pub edge_flows: Vec<Option<f32>>,
+    pub edge_numbers: Vec<Option<usize>>,
+}
+
 pub struct Node {
     id: NodeIndex,
     pos: (f32, f32),
@@ -147,26 +632,29 @@ pub struct Node {
     from_node: Option<NodeIndex>,
     curr_path: Option<f32>,
     state: NodeState,
+    /// A short label a traversal can show next to the node
+    badge: Option<String>,
+    /// Which group a traversal has put the node in, each group gets its own color
+    group: Option<usize>,
+    /// Whether the node gets an extra ring drawn around it to make it stand out
+    ring: bool,
+    attributes: Attributes,
 }
 
 impl Node {
-    pub fn new(
-        id: NodeIndex,
-        pos: (f32, f32),
-        name: String,
-        connections: Vec<(NodeIndex, Option<f32>)>,
-    ) -> Self {
+    pub fn new(id: NodeIndex, pos: (f32, f32), name: String) -> Self {
         Self {
             pos,
             name,
             state: NodeState::None,
             id,
-            edges: connections
-                .iter()
-                .map(|(idx, w)| Edge::new(id, *idx, *w))
-                .collect(),
+            edges: Vec::new(),
             from_node: None,
             curr_path: None,
+            badge: None,
+            group: None,
+            ring: false,
+            attributes: Attributes::new(),
         }
     }
 
@@ -212,14 +700,111 @@ impl Node {
         self.state = NodeState::None;
         self.curr_path = None;
         self.from_node = None;
+        self.badge = None;
+        self.group = None;
+        self.ring = false;
+
+        for edge in &mut self.edges {
+            edge.3 = EdgeState::None;
+            edge.4 = None;
+            edge.5 = None;
+        }
     }
 
     pub fn get_state(&self) -> NodeState {
         self.state
     }
 
-    pub fn add_edge(&mut self, other: NodeIndex, weight: Option<f32>) {
-        self.edges.push(Edge::new(self.id, other, weight))
+    pub fn traversal(&self) -> NodeTraversal {
+        NodeTraversal {
+            state: self.state,
+            from_node: self.from_node,
+            curr_path: self.curr_path,
+            badge: self.badge.clone(),
+            group: self.group,
+            ring: self.ring,
+            edge_states: self.edges.iter().map(|e| e.3).collect(),
+            edge_flows: self.edges.iter().map(|e| e.4).collect(),
+            edge_numbers: self.edges.iter().map(|e| e.5).collect(),
+        }
+    }
+
+    pub fn set_traversal(&mut self, traversal: NodeTraversal) {
+        self.state = traversal.state;
+        self.from_node = traversal.from_node;
+        self.curr_path = traversal.curr_path;
+        self.badge = traversal.badge;
+        self.group = traversal.group;
+        self.ring = traversal.ring;
+
+        for (((edge, state), flow), number) in self
+            .edges
+            .iter_mut()
+            .zip(traversal.edge_states)
+            .zip(traversal.edge_flows)
+            .zip(traversal.edge_numbers)
+        {
+            edge.3 = state;
+            edge.4 = flow;
+            edge.5 = number;
+        }
+    }
+
+    /// Marks the edge to `other`
+    pub fn set_edge_state(&mut self, other: NodeIndex, state: EdgeState) {
+        for edge in self.edges.iter_mut().filter(|e| e.2 == other) {
+            edge.3 = state;
+        }
+    }
+
+    /// Marks one edge to `other` with this weight, preferring one that hasn't been marked yet
+    fn set_single_edge_state(&mut self, other: NodeIndex, weight: f32, state: EdgeState) {
+        let matching = |e: &Edge| e.2 == other && e.0 == weight && e.3 != EdgeState::Accepted;
+
+        let position = self
+            .edges
+            .iter()
+            .position(|e| matching(e) && e.3 == EdgeState::None)
+            .or_else(|| self.edges.iter().position(matching));
+
+        if let Some(i) = position {
+            self.edges[i].3 = state;
+        }
+    }
+
+    /// Moves the first edge to `other` marked `from` on to `to`
+    fn change_edge_state(&mut self, other: NodeIndex, from: EdgeState, to: EdgeState) {
+        if let Some(edge) = self.edges.iter_mut().find(|e| e.2 == other && e.3 == from) {
+            edge.3 = to;
+        }
+    }
+
+    /// Shares a flow out between the edges to `other`, filling each one up in turn
+    pub fn set_edge_flow(&mut self, other: NodeIndex, flow: Option<f32>) {
+        let mut remaining = flow;
+
+        for edge in self.edges.iter_mut().filter(|e| e.2 == other) {
+            edge.4 = remaining.map(|f| f.min(edge.0));
+            remaining = remaining.map(|f| f - edge.0.min(f));
+        }
+    }
+
+    /// Numbers the first edge to `other` that doesn't have a number yet, so parallel edges each
+    /// get their own
+    fn number_edge(&mut self, other: NodeIndex, number: usize) {
+        if let Some(edge) = self
+            .edges
+            .iter_mut()
+            .find(|e| e.2 == other && e.5.is_none())
+        {
+            edge.5 = Some(number);
+        }
+    }
+
+    fn add_edge(&mut self, other: NodeIndex, weight: Option<f32>, id: EdgeId) {
+        let mut edge = Edge::new(self.id, other, weight);
+        edge.6 = id;
+        self.edges.push(edge)
     }
 
     pub fn get_curr_path(&self) -> f32 {
@@ -250,17 +835,87 @@ impl Node {
         })
     }
 
+    /// Removes the edge to `other` that was added last
+    fn remove_last_edge(&mut self, other: NodeIndex) -> Option<Edge> {
+        let i = self.edges.iter().rposition(|e| e.2 == other)?;
+        Some(self.edges.remove(i))
+    }
+
+    /// Changes the weight of the first edge to `other` that weighs `old`, returning false if
+    /// there wasn't one
+    pub fn set_edge_weight(&mut self, other: NodeIndex, old: f32, new: f32) -> bool {
+        match self.edges.iter_mut().find(|e| e.2 == other && e.0 == old) {
+            Some(edge) => {
+                edge.0 = new;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
     pub fn start(&mut self) {
         self.state = NodeState::Start;
     }
+
+    pub fn get_badge(&self) -> Option<&str> {
+        self.badge.as_deref()
+    }
+
+    pub fn set_badge(&mut self, badge: Option<String>) {
+        self.badge = badge;
+    }
+
+    pub fn get_group(&self) -> Option<usize> {
+        self.group
+    }
+
+    pub fn set_group(&mut self, group: Option<usize>) {
+        self.group = group;
+    }
+
+    pub fn has_ring(&self) -> bool {
+        self.ring
+    }
+
+    pub fn set_ring(&mut self, ring: bool) {
+        self.ring = ring;
+    }
+
+    pub fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    pub fn attributes_mut(&mut self) -> &mut Attributes {
+        &mut self.attributes
+    }
 }
 
 #[derive(Clone, Copy)]
-pub struct Edge(f32, NodeIndex, NodeIndex);
+pub struct Edge(
+    f32,
+    NodeIndex,
+    NodeIndex,
+    EdgeState,
+    Option<f32>,
+    Option<usize>,
+    EdgeId,
+);
 
 impl Edge {
     pub fn new(n1: NodeIndex, n2: NodeIndex, weight: Option<f32>) -> Self {
-        Self(weight.unwrap_or(1.0), n1, n2)
+        Self(
+            weight.unwrap_or(1.0),
+            n1,
+            n2,
+            EdgeState::None,
+            None,
+            None,
+            0,
+        )
     }
     pub fn get_nodes(&self) -> (NodeIndex, NodeIndex) {
         (self.1, self.2)
@@ -273,4 +928,23 @@ impl Edge {
     pub fn get_weight(&self) -> f32 {
         self.0
     }
+
+    pub fn get_state(&self) -> EdgeState {
+        self.3
+    }
+
+    /// How much a flow algorithm is sending along the edge
+    pub fn get_flow(&self) -> Option<f32> {
+        self.4
+    }
+
+    /// Tells the two halves of an undirected edge apart from any parallel edges
+    pub fn get_id(&self) -> EdgeId {
+        self.6
+    }
+
+    /// Where the edge comes along a path a traversal has numbered, starting from 1
+    pub fn get_number(&self) -> Option<usize> {
+        self.5
+    }
 }
@@ -0,0 +1,237 @@
+use crate::graph::{Attributes, Edge, Graph, NodeIndex, RemovedNode};
+
+/// A change to a graph that can be undone
+///
+/// Edits hold onto whatever they need to undo themselves, which gets filled in when they're applied.
+pub enum GraphEdit {
+    AddNode {
+        pos: (f32, f32),
+        name: String,
+        /// The node once it's been added
+        added: Option<NodeIndex>,
+        /// The node while it's undone, so redoing gives it back the same index
+        removed: Option<RemovedNode>,
+    },
+    RemoveNode {
+        idx: NodeIndex,
+        removed: Option<RemovedNode>,
+    },
+    AddEdge {
+        from: NodeIndex,
+        to: NodeIndex,
+        weight: Option<f32>,
+    },
+    RemoveEdge {
+        edge: Edge,
+        /// Every edge between the same nodes with its attributes, since they all get removed together
+        removed: Vec<(Edge, Attributes)>,
+    },
+    SetWeight {
+        from: NodeIndex,
+        to: NodeIndex,
+        old: f32,
+        new: f32,
+    },
+    Rename {
+        idx: NodeIndex,
+        old: String,
+        new: String,
+    },
+    Move {
+        idx: NodeIndex,
+        old: (f32, f32),
+        new: (f32, f32),
+    },
+}
+
+impl GraphEdit {
+    pub fn add_node(pos: (f32, f32), name: String) -> Self {
+        GraphEdit::AddNode {
+            pos,
+            name,
+            added: None,
+            removed: None,
+        }
+    }
+
+    pub fn remove_node(idx: NodeIndex) -> Self {
+        GraphEdit::RemoveNode { idx, removed: None }
+    }
+
+    pub fn remove_edge(edge: Edge) -> Self {
+        GraphEdit::RemoveEdge {
+            edge,
+            removed: Vec::new(),
+        }
+    }
+
+    pub fn apply(&mut self, graph: &mut Graph) {
+        match self {
+            GraphEdit::AddNode {
+                pos,
+                name,
+                added,
+                removed,
+            } => match removed {
+                Some(_) => restore(graph, removed),
+                None => *added = Some(graph.add_node(*pos, name.clone(), Vec::new())),
+            },
+            GraphEdit::RemoveNode { idx, removed } => *removed = graph.remove_node(*idx),
+            GraphEdit::AddEdge { from, to, weight } => graph.add_edge(*from, *to, *weight),
+            GraphEdit::RemoveEdge { edge, removed } => {
+                let (a, b) = edge.get_nodes();
+                *removed = graph
+                    .get_node(a)
+                    .get_edges()
+                    .into_iter()
+                    .filter(|e| e.get_nodes().1 == b)
+                    .map(|e| (e, graph.edge_attributes(&e).cloned().unwrap_or_default()))
+                    .collect();
+                graph.remove_edge(*edge);
+            }
+            GraphEdit::SetWeight { from, to, old, new } => {
+                graph.set_edge_weight(*from, *to, *old, *new);
+            }
+            GraphEdit::Rename { idx, new, .. } => {
+                if let Some(node) = graph.try_get_node_mut(*idx) {
+                    node.set_name(new.clone());
+                }
+            }
+            GraphEdit::Move { idx, new, .. } => {
+                if let Some(node) = graph.try_get_node_mut(*idx) {
+                    *node.get_pos_mut() = *new;
+                }
+            }
+        }
+    }
+
+    pub fn revert(&mut self, graph: &mut Graph) {
+        match self {
+            GraphEdit::AddNode { added, removed, .. } => {
+                if let Some(idx) = added {
+                    *removed = graph.remove_node(*idx);
+                }
+            }
+            GraphEdit::RemoveNode { removed, .. } => restore(graph, removed),
+            // Self loops are never added so there's nothing to take back
+            GraphEdit::AddEdge { from, to, .. } if from != to => graph.remove_last_edge(*from, *to),
+            GraphEdit::AddEdge { .. } => {}
+            GraphEdit::RemoveEdge { removed, .. } => {
+                for (edge, attributes) in removed.drain(..) {
+                    graph.restore_edge(edge, attributes);
+                }
+            }
+            GraphEdit::SetWeight { from, to, old, new } => {
+                graph.set_edge_weight(*from, *to, *new, *old);
+            }
+            GraphEdit::Rename { idx, old, .. } => {
+                if let Some(node) = graph.try_get_node_mut(*idx) {
+                    node.set_name(old.clone());
+                }
+            }
+            GraphEdit::Move { idx, old, .. } => {
+                if let Some(node) = graph.try_get_node_mut(*idx) {
+                    *node.get_pos_mut() = *old;
+                }
+            }
+        }
+    }
+
+    /// Whether this edit changes which nodes or edges are in the graph
+    pub fn is_structural(&self) -> bool {
+        !matches!(
+            self,
+            GraphEdit::SetWeight { .. } | GraphEdit::Rename { .. } | GraphEdit::Move { .. }
+        )
+    }
+
+    /// Folds `other` into this edit if they both change the same thing
+    fn merge(&mut self, other: &GraphEdit) -> bool {
+        match (self, other) {
+            (
+                GraphEdit::Move { idx, new, .. },
+                GraphEdit::Move {
+                    idx: other_idx,
+                    new: other_new,
+                    ..
+                },
+            ) if idx == other_idx => {
+                *new = *other_new;
+                true
+            }
+            // Parallel edges are told apart by their weight, so the next edit has to carry on
+            // from where this one left off
+            (
+                GraphEdit::SetWeight { from, to, new, .. },
+                GraphEdit::SetWeight {
+                    from: other_from,
+                    to: other_to,
+                    old: other_old,
+                    new: other_new,
+                },
+            ) if from == other_from && to == other_to && new == other_old => {
+                *new = *other_new;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Puts a removed node back, holding onto it if its slot has been taken so it isn't lost
+fn restore(graph: &mut Graph, removed: &mut Option<RemovedNode>) {
+    if let Some(node) = removed.take() {
+        if let Err(node) = graph.restore_node(node) {
+            debug_assert!(false, "tried to restore a node into a slot that's in use");
+            *removed = Some(*node);
+        }
+    }
+}
+
+/// The edits made to a graph, so they can be undone and redone
+#[derive(Default)]
+pub struct EditHistory {
+    undo: Vec<GraphEdit>,
+    redo: Vec<GraphEdit>,
+}
+
+impl EditHistory {
+    /// Records an edit that's just been applied, which means nothing can be redone anymore
+    pub fn push(&mut self, edit: GraphEdit, merge: bool) {
+        self.redo.clear();
+
+        if merge {
+            if let Some(last) = self.undo.last_mut() {
+                if last.merge(&edit) {
+                    return;
+                }
+            }
+        }
+
+        self.undo.push(edit);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn pop_undo(&mut self) -> Option<GraphEdit> {
+        self.undo.pop()
+    }
+
+    pub fn pop_redo(&mut self) -> Option<GraphEdit> {
+        self.redo.pop()
+    }
+
+    pub fn push_undo(&mut self, edit: GraphEdit) {
+        self.undo.push(edit);
+    }
+
+    pub fn push_redo(&mut self, edit: GraphEdit) {
+        self.redo.push(edit);
+    }
+}
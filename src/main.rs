@@ -1,8 +1,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod app;
+mod files;
+mod formats;
 pub mod generation;
 mod graph;
+mod history;
 pub mod menus;
 mod painter;
 mod traversers;
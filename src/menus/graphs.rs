@@ -1,7 +1,9 @@
-use egui::{ComboBox, Ui};
+use egui::{Button, ComboBox, TextEdit, Ui};
 
 use crate::{
     app::GraphApp,
+    files::{self, FileLoader},
+    formats::FileFormat,
     generation::{generators, GraphGenerator, GENERATOR_COUNT},
     menus::Menu,
 };
@@ -9,6 +11,13 @@ use crate::{
 pub struct GraphMenu {
     curr_generator: usize,
     generators: [Box<dyn GraphGenerator>; GENERATOR_COUNT],
+    file_path: String,
+    file_format: FileFormat,
+    /// The format of the file currently being opened
+    opening_format: FileFormat,
+    file_loader: FileLoader,
+    file_message: Option<String>,
+    generator_error: Option<String>,
 }
 
 impl Menu for GraphMenu {
@@ -44,6 +53,80 @@ impl Menu for GraphMenu {
             app.curr_graph = graph_selection - 1;
         }
 
+        if let Some(seed) = graphs.get(app.curr_graph).and_then(|g| g.get_seed()) {
+            ui.label(format!("Generated from seed {seed}"));
+        }
+
+        let formats = FileFormat::values();
+        let mut curr_format = formats.iter().position(|f| *f == self.file_format).unwrap();
+
+        ui.horizontal(|ui| {
+            ComboBox::new("File Format", "").show_index(ui, &mut curr_format, formats.len(), |i| {
+                formats[i].name().to_owned()
+            });
+
+            self.file_format = formats[curr_format];
+            let extension = self.file_format.extension();
+
+            if files::USES_PATHS {
+                ui.label("File");
+                TextEdit::singleline(&mut self.file_path)
+                    .hint_text(format!("graph.{extension}"))
+                    .desired_width(150.0)
+                    .show(ui);
+            }
+
+            if ui.button("Save").clicked() {
+                if let Some(graph) = graphs.get(app.curr_graph) {
+                    let path = if self.file_path.is_empty() || !files::USES_PATHS {
+                        format!("{}.{extension}", graph.get_name())
+                    } else {
+                        self.file_path.clone()
+                    };
+
+                    let contents = self.file_format.save(
+                        graph,
+                        &app.painter,
+                        app.traversal_manager.path_end(),
+                    );
+
+                    self.file_message = Some(match files::save_file(&path, &contents) {
+                        Ok(()) => format!("Saved {path}"),
+                        Err(e) => e,
+                    });
+                }
+            }
+
+            if ui
+                .add_enabled(self.file_format.can_load(), Button::new("Open"))
+                .clicked()
+            {
+                self.opening_format = self.file_format;
+
+                if let Err(e) =
+                    self.file_loader
+                        .open(ui.ctx(), &self.file_path, &format!(".{extension}"))
+                {
+                    self.file_message = Some(e);
+                }
+            }
+        });
+
+        if let Some(file) = self.file_loader.take() {
+            match self.opening_format.load(&file.contents) {
+                Ok(graph) => {
+                    graphs.push(graph);
+                    app.curr_graph = graphs.len() - 1;
+                    self.file_message = Some(format!("Opened {}", file.name));
+                }
+                Err(e) => self.file_message = Some(format!("{}: {e}", file.name)),
+            }
+        }
+
+        if let Some(message) = &self.file_message {
+            ui.label(message);
+        }
+
         ui.horizontal(|ui| {
             ui.label("Graph Generator");
             ComboBox::new("Graph Generator", "").show_index(
@@ -61,8 +144,18 @@ impl Menu for GraphMenu {
         generator.ui(ui);
 
         if ui.button("Generate Graph").clicked() {
-            graphs.push(generator.gen_graph());
-            app.curr_graph = graphs.len() - 1;
+            match generator.gen_graph() {
+                Ok(graph) => {
+                    graphs.push(graph);
+                    app.curr_graph = graphs.len() - 1;
+                    self.generator_error = None;
+                }
+                Err(e) => self.generator_error = Some(e),
+            }
+        }
+
+        if let Some(error) = &self.generator_error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
         }
     }
 
@@ -76,6 +169,12 @@ impl Default for GraphMenu {
         GraphMenu {
             curr_generator: 0,
             generators: generators(),
+            file_path: String::new(),
+            file_format: FileFormat::Json,
+            opening_format: FileFormat::Json,
+            file_loader: FileLoader::default(),
+            file_message: None,
+            generator_error: None,
         }
     }
 }
@@ -1,22 +1,53 @@
 use std::collections::HashMap;
 
-use egui::{ComboBox, DragValue, Key, TextEdit, Ui, Widget};
+use egui::{Button, ComboBox, DragValue, Key, TextEdit, Ui, Widget};
 
 use crate::{
     app::{GraphApp, INTERNAL_HEIGHT, INTERNAL_WIDTH},
     graph::Graph,
+    history::GraphEdit,
     menus::Menu,
+    traversers::TraversalManager,
 };
 
+/// Makes an edit from the menu, stopping any traversal first if it adds or removes nodes or
+/// edges the traversal could be holding onto, the same as undoing or redoing it would
+fn edit(graph: &mut Graph, manager: &mut TraversalManager, edit: GraphEdit, merge: bool) {
+    if edit.is_structural() {
+        manager.stop_traversal();
+        graph.reset();
+    }
+
+    graph.edit(edit, merge);
+}
+
 #[derive(Default)]
 pub struct NodesMenu {
     curr_adding_node_text: String,
     curr_editing_node: usize,
+    new_name: String,
+    editing_name: bool,
     node_data: Vec<NodeMenuData>,
 }
 
 impl Menu for NodesMenu {
     fn ui(&mut self, app: &mut GraphApp, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            let (can_undo, can_redo) = app.graphs.get(app.curr_graph).map_or((false, false), |g| {
+                (g.history().can_undo(), g.history().can_redo())
+            });
+
+            if ui.add_enabled(can_undo, Button::new("Undo")).clicked() {
+                app.undo();
+            }
+
+            if ui.add_enabled(can_redo, Button::new("Redo")).clicked() {
+                app.redo();
+            }
+        });
+
+        let manager = &mut app.traversal_manager;
+
         if let Some(graph) = app.graphs.get_mut(app.curr_graph) {
             ui.horizontal(|ui| {
                 ui.label("Add Node");
@@ -29,24 +60,34 @@ impl Menu for NodesMenu {
                 if (node_name_editor.response.lost_focus() || ui.button("X").clicked())
                     && !self.curr_adding_node_text.is_empty()
                 {
-                    graph.add_node(
-                        (500., 500.),
-                        std::mem::take(&mut self.curr_adding_node_text),
-                        Vec::new(),
+                    edit(
+                        graph,
+                        manager,
+                        GraphEdit::add_node(
+                            (500., 500.),
+                            std::mem::take(&mut self.curr_adding_node_text),
+                        ),
+                        false,
                     );
 
-                    self.node_data.push(NodeMenuData {
-                        adding_index: 0,
-                        removal_index: 0,
-                        weight: if graph.is_weighted() { Some(1.0) } else { None },
-                    });
-
                     if ui.input().key_pressed(Key::Enter) {
                         node_name_editor.response.request_focus()
                     }
                 }
             });
 
+            // Undo and redo can add and remove nodes behind our back
+            self.node_data.resize(
+                graph.get_nodes().len(),
+                NodeMenuData {
+                    weight: if graph.is_weighted() { Some(1.0) } else { None },
+                    ..Default::default()
+                },
+            );
+            if self.curr_editing_node > graph.get_nodes().len() {
+                self.curr_editing_node = 0;
+            }
+
             let index_name_map = graph
                 .get_nodes()
                 .iter()
@@ -63,8 +104,8 @@ impl Menu for NodesMenu {
                 .map(|idx| graph.get_node(*idx).get_name().to_owned())
                 .collect::<Vec<_>>();
 
-            let mut edge_to_add = None;
-            let mut edge_to_remove = None;
+            let mut edits = Vec::new();
+            let mut node_to_remove = None;
 
             ComboBox::from_label("Nodes").show_index(
                 ui,
@@ -83,27 +124,82 @@ impl Menu for NodesMenu {
                 return;
             }
 
-            if let Some(node) = graph.try_get_node_mut(usize_to_idx[self.curr_editing_node - 1]) {
-                let (x, y) = node.get_pos_mut();
+            if let Some(node) = graph.try_get_node(usize_to_idx[self.curr_editing_node - 1]) {
+                let id = node.get_id();
+
+                if ui.button("Remove Node").clicked() {
+                    node_to_remove = Some(id);
+                }
+
+                // Keep the name box in sync with the node unless it's being typed in
+                if !self.editing_name {
+                    self.new_name = node.get_name().to_owned();
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Name");
+                    let response = TextEdit::singleline(&mut self.new_name)
+                        .desired_width(100.0)
+                        .show(ui)
+                        .response;
+
+                    if response.lost_focus()
+                        && !self.new_name.is_empty()
+                        && self.new_name != node.get_name()
+                    {
+                        edits.push((
+                            GraphEdit::Rename {
+                                idx: id,
+                                old: node.get_name().to_owned(),
+                                new: self.new_name.clone(),
+                            },
+                            false,
+                        ));
+                    }
 
-                DragValue::new(x)
+                    self.editing_name = response.has_focus();
+                });
+
+                let old = node.get_pos();
+                let (mut x, mut y) = old;
+
+                let x_response = DragValue::new(&mut x)
                     .clamp_range(0.0..=INTERNAL_WIDTH)
                     .speed(1.0)
                     .prefix("X: ")
                     .ui(ui);
-                DragValue::new(y)
+                let y_response = DragValue::new(&mut y)
                     .clamp_range(0.0..=INTERNAL_HEIGHT)
                     .speed(1.0)
                     .prefix("Y: ")
                     .ui(ui);
 
-                let data = &mut self.node_data[usize_to_idx
-                    .iter()
-                    .position(|i| *i == node.get_id())
-                    .unwrap()];
+                for response in [x_response, y_response] {
+                    if response.changed() {
+                        // Each drag of a value only makes one undo step
+                        let merge = response.dragged() && !response.drag_started();
+                        edits.push((
+                            GraphEdit::Move {
+                                idx: id,
+                                old,
+                                new: (x, y),
+                            },
+                            merge,
+                        ));
+                    }
+                }
+
+                let data = &mut self.node_data[usize_to_idx.iter().position(|i| *i == id).unwrap()];
 
                 let edges = node.get_edges();
 
+                if data.removal_index > edges.len() {
+                    data.removal_index = 0;
+                }
+                if data.adding_index >= usize_to_idx.len() {
+                    data.adding_index = 0;
+                }
+
                 ui.horizontal(|ui| {
                     ComboBox::from_label("Remove Edge").show_index(
                         ui,
@@ -114,7 +210,7 @@ impl Menu for NodesMenu {
                                 "Select Node".to_owned()
                             } else {
                                 let nodes = edges[i - 1].get_nodes();
-                                if nodes.0 == node.get_id() {
+                                if nodes.0 == id {
                                     index_name_map[&nodes.1].clone()
                                 } else {
                                     index_name_map[&nodes.0].clone()
@@ -123,8 +219,35 @@ impl Menu for NodesMenu {
                         },
                     );
 
+                    if data.removal_index > 0 && graph.is_weighted() {
+                        let (old, from, to) = edges[data.removal_index - 1].get_weighted_nodes();
+                        let mut weight = old;
+
+                        let response = DragValue::new(&mut weight).prefix("Weight: ").ui(ui);
+                        if response.changed() {
+                            // Parallel edges with the same weight can't be told apart, so
+                            // follow whichever one the edit changes
+                            if let Some(i) = edges
+                                .iter()
+                                .position(|e| e.get_weighted_nodes() == (old, from, to))
+                            {
+                                data.removal_index = i + 1;
+                            }
+
+                            edits.push((
+                                GraphEdit::SetWeight {
+                                    from,
+                                    to,
+                                    old,
+                                    new: weight,
+                                },
+                                response.dragged() && !response.drag_started(),
+                            ));
+                        }
+                    }
+
                     if ui.button("X").clicked() && data.removal_index > 0 {
-                        edge_to_remove = Some(edges[data.removal_index - 1]);
+                        edits.push((GraphEdit::remove_edge(edges[data.removal_index - 1]), false));
                         data.removal_index -= 1;
                     }
                 });
@@ -145,18 +268,33 @@ impl Menu for NodesMenu {
                     }
 
                     if ui.button("X").clicked() {
-                        let idx = usize_to_idx[data.adding_index];
-                        edge_to_add = Some((node.get_id(), idx));
+                        let to = usize_to_idx[data.adding_index];
+
+                        if to != id {
+                            edits.push((
+                                GraphEdit::AddEdge {
+                                    from: id,
+                                    to,
+                                    weight: data.weight,
+                                },
+                                false,
+                            ));
+                        }
                     }
                 });
+            }
 
-                if let Some((a, b)) = edge_to_add {
-                    graph.add_edge(a, b, data.weight)
-                }
+            for (graph_edit, merge) in edits {
+                edit(graph, manager, graph_edit, merge);
+            }
 
-                if let Some(e) = edge_to_remove {
-                    graph.remove_edge(e)
-                }
+            if let Some(idx) = node_to_remove {
+                let position = usize_to_idx.iter().position(|i| *i == idx).unwrap();
+
+                edit(graph, manager, GraphEdit::remove_node(idx), false);
+
+                self.node_data.remove(position);
+                self.curr_editing_node = 0;
             }
         }
     }
@@ -168,11 +306,11 @@ impl Menu for NodesMenu {
     fn graph_updated(&mut self, graph: &Graph) {
         self.curr_editing_node = 0;
         let mut node_data = NodeMenuData::default();
-        
+
         if graph.is_directed() {
             node_data.weight = Some(1.0);
         }
-        
+
         self.node_data = vec![node_data; graph.get_nodes().len()];
     }
 }
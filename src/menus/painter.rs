@@ -34,6 +34,11 @@ impl Menu for GraphPainterMenu {
             ui.label("Visited Node Color");
             ui.color_edit_button_srgba(&mut painter.visited_color);
 
+            ui.add_space(10.0);
+
+            ui.label("Ring Color");
+            ui.color_edit_button_srgba(&mut painter.ring_color);
+
             ui.end_row();
 
             ui.label("Node Radius");
@@ -55,6 +60,22 @@ impl Menu for GraphPainterMenu {
             DragValue::new(&mut painter.node_text_size)
                 .clamp_range(1..=100)
                 .ui(ui);
+
+            ui.end_row();
+
+            ui.label("Group Saturation");
+            DragValue::new(&mut painter.palette_saturation)
+                .clamp_range(0.0..=1.0)
+                .speed(0.01)
+                .ui(ui);
+
+            ui.add_space(10.0);
+
+            ui.label("Group Brightness");
+            DragValue::new(&mut painter.palette_value)
+                .clamp_range(0.0..=1.0)
+                .speed(0.01)
+                .ui(ui);
         });
 
         ui.heading("Edge Settings");
@@ -73,6 +94,56 @@ impl Menu for GraphPainterMenu {
             ui.label("Path Color");
             ui.color_edit_button_srgba(&mut painter.path_color);
 
+            ui.add_space(10.0);
+
+            ui.label("Relaxed Edge Color");
+            ui.color_edit_button_srgba(&mut painter.relaxed_edge_color);
+
+            ui.end_row();
+
+            ui.label("Cycle Color");
+            ui.color_edit_button_srgba(&mut painter.cycle_color);
+
+            ui.add_space(10.0);
+
+            ui.label("Bridge Color");
+            ui.color_edit_button_srgba(&mut painter.bridge_color);
+
+            ui.end_row();
+
+            ui.label("Accepted Edge Color");
+            ui.color_edit_button_srgba(&mut painter.accepted_edge_color);
+
+            ui.add_space(10.0);
+
+            ui.label("Rejected Edge Color");
+            ui.color_edit_button_srgba(&mut painter.rejected_edge_color);
+
+            ui.end_row();
+
+            ui.label("Matched Edge Color");
+            ui.color_edit_button_srgba(&mut painter.matched_edge_color);
+
+            ui.add_space(10.0);
+
+            ui.label("Matched Edge Stroke");
+            DragValue::new(&mut painter.matched_edge_stroke).ui(ui);
+
+            ui.end_row();
+
+            ui.label("Residual Edge Color");
+            ui.color_edit_button_srgba(&mut painter.residual_edge_color);
+
+            ui.add_space(10.0);
+
+            ui.label("Show Residual Edges");
+            ui.checkbox(&mut painter.show_residual_edges, "");
+
+            ui.end_row();
+
+            ui.label("Conflict Edge Color");
+            ui.color_edit_button_srgba(&mut painter.conflict_edge_color);
+
             ui.end_row();
 
             ui.label("Arrow Length");
@@ -1,31 +1,38 @@
 use std::collections::HashMap;
 
-use egui::{ComboBox, DragValue, Ui, Widget};
+use egui::{Button, ComboBox, DragValue, Slider, Ui, Widget};
 
-use crate::{app::GraphApp, menus::Menu, traversers::GraphTraversers};
+use crate::{
+    app::GraphApp,
+    graph::NodeIndex,
+    menus::Menu,
+    traversers::{traversers, Traverser, TRAVERSER_COUNT},
+};
 
-#[derive(Default)]
 pub struct TraversalMenu {
-    pub start_node: usize,
-    pub end_node: usize,
+    pub start_node: Option<NodeIndex>,
+    pub end_node: Option<NodeIndex>,
     pub debug_view: bool,
+    curr_traverser: usize,
+    traversers: [Box<dyn Traverser>; TRAVERSER_COUNT],
+    start_error: Option<String>,
 }
 
 impl Menu for TraversalMenu {
     fn ui(&mut self, app: &mut GraphApp, ui: &mut Ui) {
+        let mut new_graph = None;
+
         if let Some(graph) = app.graphs.get_mut(app.curr_graph) {
             let manager = &mut app.traversal_manager;
-            let algs = GraphTraversers::values();
-            let mut curr_alg = algs.iter().position(|a| *a == manager.alg).unwrap();
 
             ComboBox::from_label("Traversal Algorithm").show_index(
                 ui,
-                &mut curr_alg,
-                algs.len(),
-                |i| algs[i].name().to_owned(),
+                &mut self.curr_traverser,
+                self.traversers.len(),
+                |i| self.traversers[i].name().to_owned(),
             );
 
-            manager.alg = algs[curr_alg];
+            self.traversers[self.curr_traverser].settings_ui(ui);
 
             let nodes = graph.get_nodes();
 
@@ -35,31 +42,72 @@ impl Menu for TraversalMenu {
                 .collect::<HashMap<_, _>>();
             let usize_to_idx = nodes.iter().map(|n| n.get_id()).collect::<Vec<_>>();
 
-            ComboBox::from_label("Start Node").show_index(
-                ui,
-                &mut self.start_node,
-                usize_to_idx.len() + 1,
-                |i| {
-                    if i == 0 {
-                        "Select Node".to_owned()
-                    } else {
-                        (*idx_to_name.get(&usize_to_idx[i - 1]).unwrap()).to_owned()
-                    }
-                },
-            );
+            // The combo boxes work on positions, so we convert to and from our stored indices.
+            // If a selected node was removed it won't be found and the selection is cleared.
+            let mut start_node = self
+                .start_node
+                .and_then(|idx| usize_to_idx.iter().position(|i| *i == idx))
+                .map_or(0, |p| p + 1);
+            let mut end_node = self
+                .end_node
+                .and_then(|idx| usize_to_idx.iter().position(|i| *i == idx))
+                .map_or(0, |p| p + 1);
 
-            ComboBox::from_label("End Node").show_index(
-                ui,
-                &mut self.end_node,
-                usize_to_idx.len() + 1,
-                |i| {
-                    if i == 0 {
-                        "Select Node".to_owned()
-                    } else {
-                        (*idx_to_name.get(&usize_to_idx[i - 1]).unwrap()).to_owned()
-                    }
-                },
-            );
+            let traverser = &self.traversers[self.curr_traverser];
+            let (uses_start_node, uses_end_node) =
+                (traverser.uses_start_node(), traverser.uses_end_node());
+
+            if uses_start_node {
+                ComboBox::from_label("Start Node").show_index(
+                    ui,
+                    &mut start_node,
+                    usize_to_idx.len() + 1,
+                    |i| {
+                        if i == 0 {
+                            "Select Node".to_owned()
+                        } else {
+                            (*idx_to_name.get(&usize_to_idx[i - 1]).unwrap()).to_owned()
+                        }
+                    },
+                );
+            }
+
+            if uses_end_node {
+                ComboBox::from_label("End Node").show_index(
+                    ui,
+                    &mut end_node,
+                    usize_to_idx.len() + 1,
+                    |i| {
+                        if i == 0 {
+                            "Select Node".to_owned()
+                        } else {
+                            (*idx_to_name.get(&usize_to_idx[i - 1]).unwrap()).to_owned()
+                        }
+                    },
+                );
+            }
+
+            self.start_node = start_node.checked_sub(1).map(|p| usize_to_idx[p]);
+            self.end_node = end_node.checked_sub(1).map(|p| usize_to_idx[p]);
+
+            // Handy for mazes and grids, which are usually crossed from corner to corner
+            if uses_start_node && ui.button("Use Top Left and Bottom Right").clicked() {
+                // The node furthest along towards a corner, going by how far right plus down it is
+                let corner = |direction: f32| {
+                    nodes
+                        .iter()
+                        .max_by(|a, b| {
+                            let along = |pos: (f32, f32)| direction * (pos.0 + pos.1);
+                            along(a.get_pos()).total_cmp(&along(b.get_pos()))
+                        })
+                        .map(|n| n.get_id())
+                };
+
+                self.start_node = corner(-1.0);
+                if uses_end_node {
+                    self.end_node = corner(1.0);
+                }
+            }
 
             ui.checkbox(&mut manager.auto, "Automatically Traverse");
 
@@ -70,27 +118,101 @@ impl Menu for TraversalMenu {
                         .clamp_range(0..=20)
                         .ui(ui);
                 });
-            } else if manager.currently_traversing && ui.button("Step Traversal").clicked() {
-                manager.update(graph);
             }
 
             if manager.traversal.is_some() {
+                // Moving through the traversal by hand pauses automatic traversal
+                ui.horizontal(|ui| {
+                    let can_step_back = manager.current_step() > 0;
+                    let can_step = manager.currently_traversing;
+
+                    if ui
+                        .add_enabled(can_step_back, Button::new("Jump to Start"))
+                        .clicked()
+                    {
+                        manager.auto = false;
+                        manager.jump_to_start(graph);
+                    }
+
+                    if ui
+                        .add_enabled(can_step_back, Button::new("Step Back"))
+                        .clicked()
+                    {
+                        manager.auto = false;
+                        manager.step_back(graph);
+                    }
+
+                    if ui
+                        .add_enabled(can_step, Button::new("Step Traversal"))
+                        .clicked()
+                    {
+                        manager.auto = false;
+                        manager.step_forward(graph);
+                    }
+
+                    if ui
+                        .add_enabled(can_step, Button::new("Jump to End"))
+                        .clicked()
+                    {
+                        manager.auto = false;
+                        manager.jump_to_end(graph);
+                    }
+                });
+
+                let mut step = manager.current_step();
+                if ui
+                    .add(Slider::new(&mut step, 0..=manager.known_steps()).text("Step"))
+                    .changed()
+                {
+                    manager.auto = false;
+                    manager.seek(step, graph);
+                }
+
                 if ui.button("Stop Traversal").clicked() {
-                    manager.traversal = None;
+                    manager.stop_traversal();
                     graph.reset();
                 }
             } else {
                 // Isn't collapsible because the button call has side effects
                 // You're not supposed to do this but /shrug
                 #[allow(clippy::collapsible_else_if)]
-                if ui.button("Start Traversal").clicked()
-                    && self.start_node > 0
-                    && self.end_node > 0
-                {
-                    manager.new_traversal(
-                        usize_to_idx[self.start_node - 1],
-                        usize_to_idx[self.end_node - 1],
-                    );
+                if ui.button("Start Traversal").clicked() {
+                    self.start_error = match (self.start_node, self.end_node) {
+                        (None, _) if uses_start_node => {
+                            Some("Pick a node to start from".to_owned())
+                        }
+                        (_, None) if uses_end_node => Some("Pick a node to end at".to_owned()),
+                        (start, end) => manager
+                            .new_traversal(
+                                self.traversers[self.curr_traverser].box_clone(),
+                                graph,
+                                start.filter(|_| uses_start_node),
+                                end.filter(|_| uses_end_node),
+                            )
+                            .err(),
+                    };
+                }
+            }
+
+            if let Some(error) = &self.start_error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+
+            if let Some(traversal) = &manager.traversal {
+                if traversal.uses_start_node() {
+                    ui.label(format!("Nodes expanded: {}", traversal.nodes_expanded()));
+                }
+                traversal.results_ui(graph, ui);
+
+                if traversal.finished() {
+                    if let Some(derived) = traversal.derived_graph(graph) {
+                        if ui
+                            .button(format!("Add {} to Graphs", derived.get_name()))
+                            .clicked()
+                        {
+                            new_graph = Some(derived);
+                        }
+                    }
                 }
             }
 
@@ -103,9 +225,9 @@ impl Menu for TraversalMenu {
                         ui.monospace(format!(
                             "{:?}",
                             traversal
-                                .to_traverse
+                                .frontier()
                                 .iter()
-                                .map(|(_, id)| graph.get_node(*id).get_name())
+                                .map(|id| graph.get_node(*id).get_name())
                                 .collect::<Vec<_>>()
                         ))
                     });
@@ -115,7 +237,7 @@ impl Menu for TraversalMenu {
                         ui.monospace(format!(
                             "{:?}",
                             traversal
-                                .visited
+                                .visited()
                                 .iter()
                                 .map(|id| graph.get_node(*id).get_name())
                                 .collect::<Vec<_>>()
@@ -124,6 +246,10 @@ impl Menu for TraversalMenu {
                 }
             }
         }
+
+        if let Some(graph) = new_graph {
+            app.graphs.push(graph);
+        }
     }
 
     fn name(&self) -> &'static str {
@@ -131,7 +257,21 @@ impl Menu for TraversalMenu {
     }
 
     fn graph_updated(&mut self, _graph: &crate::graph::Graph) {
-        self.start_node = 0;
-        self.end_node = 0;
+        self.start_node = None;
+        self.end_node = None;
+        self.start_error = None;
+    }
+}
+
+impl Default for TraversalMenu {
+    fn default() -> Self {
+        TraversalMenu {
+            start_node: None,
+            end_node: None,
+            debug_view: false,
+            curr_traverser: 0,
+            traversers: traversers(),
+            start_error: None,
+        }
     }
 }
@@ -1,13 +1,98 @@
-use std::ops::Sub;
+use std::{collections::HashSet, ops::Sub};
 
 use eframe::epaint::QuadraticBezierShape;
-use egui::{Align2, Color32, FontId, Painter, Pos2, Rgba, Rounding, Stroke, Vec2};
+use egui::{color::Hsva, Align2, Color32, FontId, Painter, Pos2, Rounding, Stroke, Vec2};
 
 use crate::{
     app::{INTERNAL_HEIGHT, INTERNAL_WIDTH},
-    graph::{Edge, Graph, Node, NodeIndex, NodeState},
+    graph::{Edge, EdgeState, Graph, Node, NodeIndex, NodeState},
 };
 
+/// Something [`GraphPainter`] can draw onto
+///
+/// Positions are in the canvas' own coordinates, use [`Canvas::scale`] to get there from ours.
+pub trait Canvas {
+    /// How much internal coordinates need to be scaled by to fit the canvas
+    fn scale(&self) -> Vec2;
+
+    fn line_segment(&mut self, points: [Pos2; 2], stroke: Stroke);
+
+    fn quadratic_bezier(&mut self, points: [Pos2; 3], stroke: Stroke);
+
+    fn circle(&mut self, center: Pos2, radius: f32, fill: Color32, stroke: Stroke);
+
+    /// Draws text centered on `pos`, with a rectangle behind it if there's a background color
+    fn text(
+        &mut self,
+        pos: Pos2,
+        text: &str,
+        size: f32,
+        color: Color32,
+        background: Option<Color32>,
+    );
+}
+
+impl Canvas for Painter {
+    fn scale(&self) -> Vec2 {
+        let window_size = self.ctx().available_rect().size();
+        Vec2::new(
+            window_size.x / INTERNAL_WIDTH,
+            window_size.y / INTERNAL_HEIGHT,
+        )
+    }
+
+    fn line_segment(&mut self, points: [Pos2; 2], stroke: Stroke) {
+        Painter::line_segment(self, points, stroke);
+    }
+
+    fn quadratic_bezier(&mut self, points: [Pos2; 3], stroke: Stroke) {
+        self.add(QuadraticBezierShape::from_points_stroke(
+            points,
+            false,
+            Color32::TRANSPARENT,
+            stroke,
+        ));
+    }
+
+    fn circle(&mut self, center: Pos2, radius: f32, fill: Color32, stroke: Stroke) {
+        Painter::circle(self, center, radius, fill, stroke);
+    }
+
+    fn text(
+        &mut self,
+        pos: Pos2,
+        text: &str,
+        size: f32,
+        color: Color32,
+        background: Option<Color32>,
+    ) {
+        let Some(background) = background else {
+            Painter::text(
+                self,
+                pos,
+                Align2::CENTER_CENTER,
+                text,
+                FontId::monospace(size),
+                color,
+            );
+            return;
+        };
+
+        let galley = self.layout_no_wrap(text.to_owned(), FontId::monospace(size), color);
+
+        let text_rect = galley.rect;
+        let offset = Vec2::new(text_rect.width() / 2.0, text_rect.height() / 2.0);
+
+        self.rect_filled(
+            text_rect.translate(pos.to_vec2() - offset),
+            Rounding::none(),
+            background,
+        );
+
+        self.galley(pos - offset, galley)
+    }
+}
+
 pub struct GraphPainter {
     pub start_color: Color32,
     pub node_color: Color32,
@@ -19,9 +104,22 @@ pub struct GraphPainter {
     pub visited_color: Color32,
     pub end_node_color: Color32,
     pub path_color: Color32,
+    pub relaxed_edge_color: Color32,
+    pub cycle_color: Color32,
+    pub accepted_edge_color: Color32,
+    pub rejected_edge_color: Color32,
+    pub bridge_color: Color32,
+    pub matched_edge_color: Color32,
+    pub conflict_edge_color: Color32,
+    pub ring_color: Color32,
+    pub residual_edge_color: Color32,
+    pub show_residual_edges: bool,
+    pub palette_saturation: f32,
+    pub palette_value: f32,
     pub node_radius: f32,
     pub node_stroke: f32,
     pub edge_stroke: f32,
+    pub matched_edge_stroke: f32,
     pub node_text_size: u8,
     pub weight_text_size: u8,
     pub arrow_length: f32,
@@ -29,7 +127,7 @@ pub struct GraphPainter {
 }
 
 impl GraphPainter {
-    pub fn paint_graph(&self, graph: &Graph, painter: &Painter) {
+    pub fn paint_graph(&self, graph: &Graph, canvas: &mut impl Canvas) {
         let mut weights_to_render = Vec::new();
 
         for node in graph.get_nodes() {
@@ -37,116 +135,225 @@ impl GraphPainter {
                 self.paint_graph_edge(
                     graph,
                     &edge,
-                    painter,
+                    canvas,
                     graph.is_directed(),
-                    Stroke::from((self.edge_stroke, self.edge_color)),
+                    self.edge_line_stroke(edge.get_state()),
                     &mut weights_to_render,
                 )
             }
         }
 
-        for (pos, weight) in weights_to_render {
-            let text = weight.to_string();
+        if self.show_residual_edges {
+            self.paint_residual_edges(graph, canvas, &mut weights_to_render);
+        }
 
-            let galley = painter.layout_no_wrap(
-                text,
-                FontId::monospace(self.weight_text_size as f32),
+        for (pos, label) in weights_to_render {
+            canvas.text(
+                pos,
+                &label,
+                self.weight_text_size as f32,
                 self.weight_text_color,
+                Some(self.text_background_color),
             );
+        }
 
-            let text_rect = galley.rect;
+        for node in graph.get_nodes() {
+            self.paint_graph_node(node, canvas)
+        }
+    }
 
-            painter.rect_filled(
-                text_rect.translate(
-                    Vec2::new(pos.x, pos.y)
-                        - Vec2::new(text_rect.width() / 2.0, text_rect.height() / 2.0),
-                ),
-                Rounding::none(),
-                self.text_background_color,
-            );
+    /// Draws an edge going backwards for each edge with something flowing along it, showing
+    /// how much of that flow could be sent back. Each half of an undirected edge holds the flow
+    /// going its own way, so both are looked at.
+    fn paint_residual_edges(
+        &self,
+        graph: &Graph,
+        canvas: &mut impl Canvas,
+        weights_to_render: &mut Vec<(Pos2, String)>,
+    ) {
+        let Vec2 { x: sf_x, y: sf_y } = canvas.scale();
+        let stroke = Stroke::new(self.edge_stroke / 2.0, self.residual_edge_color);
 
-            painter.galley(
-                pos - Vec2::new(text_rect.width() / 2.0, text_rect.height() / 2.0),
-                galley,
-            )
-        }
+        for edge in graph.get_nodes().iter().flat_map(|n| n.get_edges()) {
+            let Some(flow) = edge.get_flow().filter(|f| *f > 0.0) else {
+                continue;
+            };
 
-        for node in graph.get_nodes() {
-            self.paint_graph_node(node, painter)
+            let (a, b) = edge.get_nodes();
+            let (x1, y1) = graph.get_node(a).get_pos();
+            let (x2, y2) = graph.get_node(b).get_pos();
+
+            self.curved_arrow(
+                canvas,
+                Pos2::new(x2 * sf_x, y2 * sf_y),
+                Pos2::new(x1 * sf_x, y1 * sf_y),
+                Some(flow.to_string()),
+                stroke,
+                weights_to_render,
+            );
         }
     }
 
-    pub fn paint_path(&self, end_node: NodeIndex, graph: &Graph, painter: &Painter) {
-        let mut curr_node = graph.get_node(end_node);
-        let Some(mut next_node) = curr_node.get_last_node().map(|n| graph.get_node(n)) else {
+    pub fn paint_path(&self, end_node: NodeIndex, graph: &Graph, canvas: &mut impl Canvas) {
+        let Some(mut curr_node) = graph.try_get_node(end_node) else {
             return;
         };
 
-        while curr_node.get_id() != next_node.get_id() {
+        let mut path = vec![end_node];
+        // Stops us going round forever if the path loops back on itself
+        let mut followed = HashSet::new();
+
+        while let Some(next_node) = curr_node.get_last_node() {
+            if next_node == curr_node.get_id() || !followed.insert(curr_node.get_id()) {
+                break;
+            }
+
+            path.push(next_node);
+            curr_node = graph.get_node(next_node);
+        }
+
+        path.reverse();
+        self.paint_node_path(&path, graph, canvas);
+    }
+
+    /// Draws a path going through `path` in order, in the path color
+    pub fn paint_node_path(&self, path: &[NodeIndex], graph: &Graph, canvas: &mut impl Canvas) {
+        for pair in path.windows(2) {
             self.paint_graph_edge(
                 graph,
-                &Edge::new(next_node.get_id(), curr_node.get_id(), None),
-                painter,
+                &Edge::new(pair[0], pair[1], None),
+                canvas,
                 graph.is_directed(),
                 Stroke::from((self.edge_stroke, self.path_color)),
                 &mut Vec::new(),
             );
-
-            curr_node = next_node;
-            next_node = if let Some(node) = curr_node.get_last_node() {
-                graph.get_node(node)
-            } else {
-                return;
-            };
         }
     }
 
-    fn paint_graph_node(&self, node: &Node, painter: &Painter) {
-        let window_size = painter.ctx().available_rect().size();
-        let sf_x = window_size.x / INTERNAL_WIDTH;
-        let sf_y = window_size.y / INTERNAL_HEIGHT;
+    fn paint_graph_node(&self, node: &Node, canvas: &mut impl Canvas) {
+        let Vec2 { x: sf_x, y: sf_y } = canvas.scale();
 
         let (nx, ny) = node.get_pos();
 
         let scaled_node_pos = (nx * sf_x, ny * sf_y);
 
-        painter.circle(
+        if node.has_ring() {
+            canvas.circle(
+                scaled_node_pos.into(),
+                self.node_radius + self.node_stroke * 3.0,
+                Color32::TRANSPARENT,
+                Stroke::new(self.node_stroke * 2.0, self.ring_color),
+            );
+        }
+
+        canvas.circle(
             scaled_node_pos.into(),
             self.node_radius,
-            match node.get_state() {
-                NodeState::None => Rgba::BLACK,
-                NodeState::Start => Rgba::from(self.start_color),
-                NodeState::Seen => Rgba::from(self.seen_color),
-                NodeState::Visited => Rgba::from(self.visited_color),
-                NodeState::End => Rgba::from(self.end_node_color),
-            },
-            (self.node_stroke, self.node_color),
+            self.node_fill(node),
+            Stroke::new(self.node_stroke, self.node_color),
         );
 
-        painter.text(
+        canvas.text(
             scaled_node_pos.into(),
-            Align2::CENTER_CENTER,
             node.get_name(),
-            FontId::monospace(self.node_text_size as f32),
+            self.node_text_size as f32,
             self.node_text_color,
+            None,
         );
+
+        if let Some(badge) = node.get_badge() {
+            canvas.text(
+                Pos2::from(scaled_node_pos) + Vec2::new(self.node_radius, -self.node_radius),
+                badge,
+                self.weight_text_size as f32,
+                self.weight_text_color,
+                Some(self.text_background_color),
+            );
+        }
+    }
+
+    /// The color a node is filled with, which is its group's color if it's been put in one
+    pub fn node_fill(&self, node: &Node) -> Color32 {
+        node.get_group()
+            .map_or(self.node_fill_color(node.get_state()), |g| {
+                self.group_color(g)
+            })
+    }
+
+    pub fn node_fill_color(&self, state: NodeState) -> Color32 {
+        match state {
+            NodeState::None => Color32::BLACK,
+            NodeState::Start => self.start_color,
+            NodeState::Seen => self.seen_color,
+            NodeState::Visited => self.visited_color,
+            NodeState::End => self.end_node_color,
+        }
+    }
+
+    /// Picks a color for a group of nodes, stepping round the color wheel so that groups
+    /// next to each other in the order get very different colors
+    pub fn group_color(&self, group: usize) -> Color32 {
+        let hue = (group as f32 * 0.618_034).fract();
+        Hsva::new(hue, self.palette_saturation, self.palette_value, 1.0).into()
+    }
+
+    /// Matched edges are drawn thicker so the matching stands out
+    pub fn edge_line_stroke(&self, state: EdgeState) -> Stroke {
+        let width = if state == EdgeState::Matched {
+            self.matched_edge_stroke
+        } else {
+            self.edge_stroke
+        };
+
+        Stroke::new(width, self.edge_stroke_color(state))
+    }
+
+    pub fn edge_stroke_color(&self, state: EdgeState) -> Color32 {
+        match state {
+            EdgeState::None => self.edge_color,
+            EdgeState::Relaxed => self.relaxed_edge_color,
+            EdgeState::Cycle => self.cycle_color,
+            EdgeState::Accepted => self.accepted_edge_color,
+            EdgeState::Rejected => self.rejected_edge_color,
+            EdgeState::Bridge => self.bridge_color,
+            EdgeState::Matched => self.matched_edge_color,
+            EdgeState::Conflict => self.conflict_edge_color,
+        }
     }
 
     fn paint_graph_edge(
         &self,
         graph: &Graph,
         edge: &Edge,
-        painter: &Painter,
+        canvas: &mut impl Canvas,
         is_directed: bool,
         line_stroke: Stroke,
-        weights_to_render: &mut Vec<(Pos2, f32)>,
+        weights_to_render: &mut Vec<(Pos2, String)>,
     ) {
-        let window_size = painter.ctx().available_rect().size();
-        let sf_x = window_size.x / 1000.0;
-        let sf_y = window_size.y / 1000.0;
+        let Vec2 { x: sf_x, y: sf_y } = canvas.scale();
 
         let (weight, n1, n2) = edge.get_weighted_nodes();
 
+        // Both halves of an undirected edge are labelled in the same place, so they show the
+        // flow of whichever half it's going along
+        let flow = edge.get_flow().map(|flow| {
+            graph
+                .get_node(n2)
+                .get_edges()
+                .iter()
+                .filter(|e| !is_directed && e.get_id() == edge.get_id())
+                .filter_map(|e| e.get_flow())
+                .fold(flow, f32::max)
+        });
+
+        // Edges carrying a flow show it against their capacity, and numbered edges show where
+        // they come along the path
+        let label = match (edge.get_number(), flow) {
+            (Some(number), _) => Some(format!("#{number}")),
+            (None, Some(flow)) => Some(format!("{flow}/{weight}")),
+            (None, None) => graph.is_weighted().then(|| weight.to_string()),
+        };
+
         let n1 = graph.get_node(n1);
         let n2 = graph.get_node(n2);
 
@@ -176,51 +383,48 @@ impl GraphPainter {
                 nodes.1 == n1.get_id()
             }) {
                 self.curved_arrow(
-                    painter,
+                    canvas,
                     Pos2::new(x1 * sf_x, y1 * sf_y),
                     Pos2::new(x2 * sf_x, y2 * sf_y),
-                    weight,
-                    graph.is_weighted(),
+                    label,
                     line_stroke,
                     weights_to_render,
                 );
             } else {
                 self.arrow(
-                    painter,
+                    canvas,
                     new_n1.into(),
                     Vec2::from(new_n2).sub(new_n1.into()),
                     line_stroke,
                 );
 
-                if graph.is_weighted() {
+                if let Some(label) = label {
                     let x_text_pos = (n1.get_pos().0 + n2.get_pos().0) / 2.0 * sf_x;
                     let y_text_pos = ((n1.get_pos().1 + n2.get_pos().1) / 2.0 + 10.0) * sf_y;
 
-                    weights_to_render.push((Pos2::new(x_text_pos, y_text_pos), weight))
+                    weights_to_render.push((Pos2::new(x_text_pos, y_text_pos), label))
                 }
             }
         } else {
-            painter.line_segment([new_n1.into(), new_n2.into()], line_stroke);
+            canvas.line_segment([new_n1.into(), new_n2.into()], line_stroke);
 
-            if graph.is_weighted() {
+            if let Some(label) = label {
                 let x_text_pos = (n1.get_pos().0 + n2.get_pos().0) / 2.0 * sf_x;
                 let y_text_pos = ((n1.get_pos().1 + n2.get_pos().1) / 2.0 + 10.0) * sf_y;
 
-                weights_to_render.push((Pos2::new(x_text_pos, y_text_pos), weight))
+                weights_to_render.push((Pos2::new(x_text_pos, y_text_pos), label))
             }
         }
     }
 
-    #[allow(clippy::too_many_arguments)]
     fn curved_arrow(
         &self,
-        painter: &Painter,
+        canvas: &mut impl Canvas,
         a: Pos2,
         b: Pos2,
-        weight: f32,
-        is_weighted: bool,
+        label: Option<String>,
         line_stroke: Stroke,
-        weights_to_render: &mut Vec<(Pos2, f32)>,
+        weights_to_render: &mut Vec<(Pos2, String)>,
     ) {
         // Find midpoint
         let mid_point_x = (a.x + b.x) / 2.0;
@@ -259,25 +463,26 @@ impl GraphPainter {
             radius * -f32::sin(end_point_angle) * is_negative_dx + b.y,
         );
 
-        let curve = QuadraticBezierShape::from_points_stroke(
-            [start_point, control_point, end_point],
-            false,
-            Color32::TRANSPARENT,
-            line_stroke,
-        );
+        let points = [start_point, control_point, end_point];
 
-        if is_weighted {
-            weights_to_render.push((curve.sample(0.5), weight));
+        if let Some(label) = label {
+            let curve = QuadraticBezierShape::from_points_stroke(
+                points,
+                false,
+                Color32::TRANSPARENT,
+                line_stroke,
+            );
+            weights_to_render.push((curve.sample(0.5), label));
         }
 
-        painter.add(curve);
+        canvas.quadratic_bezier(points, line_stroke);
 
         // We want to imitate drawing an arrow from the control point to the end point
         let control_end_x = end_point.x - control_point.x;
         let control_end_y = end_point.y - control_point.y;
 
         self.arrow_pointy_bits(
-            painter,
+            canvas,
             control_point,
             Vec2::new(control_end_x, control_end_y),
             line_stroke,
@@ -286,28 +491,28 @@ impl GraphPainter {
 
     // egui's arrow() has the tips grow in size based on magnitude.
     // we want them to be constant so we recreate their function here
-    fn arrow(&self, painter: &Painter, origin: Pos2, vec: Vec2, stroke: Stroke) {
+    fn arrow(&self, canvas: &mut impl Canvas, origin: Pos2, vec: Vec2, stroke: Stroke) {
         use egui::emath::*;
         let rot = Rot2::from_angle(std::f32::consts::TAU / 10.0);
         let tip_length = self.arrow_length;
         let tip = origin + vec;
         let dir = vec.normalized();
 
-        painter.line_segment([origin, tip], stroke);
-        painter.line_segment([tip, tip - tip_length * (rot * dir)], stroke);
-        painter.line_segment([tip, tip - tip_length * (rot.inverse() * dir)], stroke);
+        canvas.line_segment([origin, tip], stroke);
+        canvas.line_segment([tip, tip - tip_length * (rot * dir)], stroke);
+        canvas.line_segment([tip, tip - tip_length * (rot.inverse() * dir)], stroke);
     }
 
     // Same as Self::arrow except we don't draw the line from the start point and end point
-    fn arrow_pointy_bits(&self, painter: &Painter, origin: Pos2, vec: Vec2, stroke: Stroke) {
+    fn arrow_pointy_bits(&self, canvas: &mut impl Canvas, origin: Pos2, vec: Vec2, stroke: Stroke) {
         use egui::emath::*;
         let rot = Rot2::from_angle(std::f32::consts::TAU / 10.0);
         let tip_length = self.arrow_length;
         let tip = origin + vec;
         let dir = vec.normalized();
 
-        painter.line_segment([tip, tip - tip_length * (rot * dir)], stroke);
-        painter.line_segment([tip, tip - tip_length * (rot.inverse() * dir)], stroke);
+        canvas.line_segment([tip, tip - tip_length * (rot * dir)], stroke);
+        canvas.line_segment([tip, tip - tip_length * (rot.inverse() * dir)], stroke);
     }
 }
 
@@ -324,9 +529,22 @@ impl Default for GraphPainter {
             visited_color: Color32::DARK_GREEN,
             end_node_color: Color32::DARK_BLUE,
             path_color: Color32::GOLD,
+            relaxed_edge_color: Color32::LIGHT_BLUE,
+            cycle_color: Color32::from_rgb(255, 0, 255),
+            accepted_edge_color: Color32::GREEN,
+            rejected_edge_color: Color32::DARK_GRAY,
+            bridge_color: Color32::from_rgb(255, 140, 0),
+            matched_edge_color: Color32::WHITE,
+            conflict_edge_color: Color32::from_rgb(0, 255, 255),
+            ring_color: Color32::RED,
+            residual_edge_color: Color32::GRAY,
+            show_residual_edges: false,
+            palette_saturation: 0.7,
+            palette_value: 0.85,
             node_radius: 32.0,
             node_stroke: 6.0,
             edge_stroke: 5.0,
+            matched_edge_stroke: 10.0,
             node_text_size: 18,
             weight_text_size: 18,
             arrow_length: 15.0,
@@ -0,0 +1,186 @@
+use egui::{Grid, RichText, ScrollArea, Ui};
+
+use crate::graph::{Graph, NodeIndex};
+
+/// The distance matrix is drawn every frame and copied at every checkpoint of a traversal, so
+/// it can't have many more rows than this
+pub const MAX_NODES: usize = 100;
+
+/// Explains why an all pairs traverser can't be used if its distance matrix would be too big
+pub fn check_matrix_size(graph: &Graph, name: &str) -> Result<(), String> {
+    if graph.get_nodes().len() > MAX_NODES {
+        Err(format!(
+            "{name} fills in a distance for every pair of nodes so it's limited to {MAX_NODES} \
+             nodes"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The shortest distances between every pair of nodes found so far, along with enough to
+/// rebuild the paths
+#[derive(Clone, Default)]
+pub struct DistanceMatrix {
+    /// Which node each row and column is for
+    pub nodes: Vec<NodeIndex>,
+    /// `distances[i][j]` is the distance from `nodes[i]` to `nodes[j]`
+    distances: Vec<Vec<f32>>,
+    /// `previous[i][j]` is the node just before `nodes[j]` on the path from `nodes[i]`
+    previous: Vec<Vec<Option<usize>>>,
+    /// The cells changed by the last step
+    pub updated: Vec<(usize, usize)>,
+}
+
+impl DistanceMatrix {
+    /// Starts off knowing only that every node is no distance from itself
+    pub fn new(graph: &Graph) -> Self {
+        let nodes = graph
+            .get_nodes()
+            .iter()
+            .map(|n| n.get_id())
+            .collect::<Vec<_>>();
+        let len = nodes.len();
+
+        let mut distances = vec![vec![f32::INFINITY; len]; len];
+        for (i, row) in distances.iter_mut().enumerate() {
+            row[i] = 0.0;
+        }
+
+        DistanceMatrix {
+            nodes,
+            distances,
+            previous: vec![vec![None; len]; len],
+            updated: Vec::new(),
+        }
+    }
+
+    pub fn position(&self, idx: NodeIndex) -> Option<usize> {
+        self.nodes.iter().position(|n| *n == idx)
+    }
+
+    pub fn get(&self, i: usize, j: usize) -> f32 {
+        self.distances[i][j]
+    }
+
+    pub fn get_previous(&self, i: usize, j: usize) -> Option<usize> {
+        self.previous[i][j]
+    }
+
+    /// Records a shorter path from `i` to `j` that comes in from `previous`
+    pub fn set(&mut self, i: usize, j: usize, distance: f32, previous: usize) {
+        self.distances[i][j] = distance;
+        self.previous[i][j] = Some(previous);
+        self.updated.push((i, j));
+    }
+
+    /// Rebuilds the shortest path found so far from `a` to `b`
+    pub fn path(&self, a: NodeIndex, b: NodeIndex) -> Option<Vec<NodeIndex>> {
+        let (i, mut j) = (self.position(a)?, self.position(b)?);
+        let mut path = vec![j];
+
+        while j != i {
+            j = self.previous[i][j]?;
+            path.push(j);
+
+            // Only a negative cycle could make the path this long
+            if path.len() > self.nodes.len() {
+                return None;
+            }
+        }
+
+        path.reverse();
+        Some(path.into_iter().map(|p| self.nodes[p]).collect())
+    }
+
+    /// How far each node is from the node furthest away from it
+    pub fn eccentricities(&self) -> Vec<f32> {
+        self.distances
+            .iter()
+            .map(|row| row.iter().copied().fold(0.0, f32::max))
+            .collect()
+    }
+}
+
+fn distance_text(distance: f32) -> String {
+    if distance.is_finite() {
+        distance.to_string()
+    } else {
+        "∞".to_owned()
+    }
+}
+
+/// Shows every distance in a grid, with the cells that just changed highlighted
+///
+/// Clicking a cell selects the path between its nodes so it can be drawn, or unselects it if
+/// it was already selected.
+pub fn distance_matrix_ui(
+    ui: &mut Ui,
+    graph: &Graph,
+    matrix: &DistanceMatrix,
+    selected: &mut Option<(NodeIndex, NodeIndex)>,
+) {
+    // The matrix can outlive nodes for a frame if they're removed while it's showing
+    let name = |idx: &NodeIndex| graph.try_get_node(*idx).map_or("?", |n| n.get_name());
+
+    ui.label("Click a distance to show its path, rows are where the path starts");
+
+    ScrollArea::both().max_height(400.0).show(ui, |ui| {
+        Grid::new("distance matrix").striped(true).show(ui, |ui| {
+            ui.label("");
+            for idx in &matrix.nodes {
+                ui.strong(name(idx));
+            }
+            ui.end_row();
+
+            for (i, a) in matrix.nodes.iter().enumerate() {
+                ui.strong(name(a));
+
+                for (j, b) in matrix.nodes.iter().enumerate() {
+                    let mut text = RichText::new(distance_text(matrix.get(i, j))).monospace();
+                    if matrix.updated.contains(&(i, j)) {
+                        text = text.color(ui.visuals().warn_fg_color).strong();
+                    }
+
+                    let is_selected = *selected == Some((*a, *b));
+                    if ui.selectable_label(is_selected, text).clicked() {
+                        *selected = (!is_selected).then_some((*a, *b));
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    });
+}
+
+/// Shows each node's eccentricity along with the diameter, radius and center of the graph
+pub fn eccentricity_ui(ui: &mut Ui, graph: &Graph, matrix: &DistanceMatrix) {
+    let eccentricities = matrix.eccentricities();
+    let diameter = eccentricities.iter().copied().fold(0.0, f32::max);
+    let radius = eccentricities.iter().copied().fold(f32::INFINITY, f32::min);
+
+    let center = matrix
+        .nodes
+        .iter()
+        .zip(&eccentricities)
+        .filter(|(_, e)| **e == radius)
+        .filter_map(|(n, _)| graph.try_get_node(*n).map(|n| n.get_name()))
+        .collect::<Vec<_>>();
+
+    ui.label(format!(
+        "Diameter: {}, radius: {}",
+        distance_text(diameter),
+        distance_text(radius)
+    ));
+    ui.label(format!("Center: {}", center.join(", ")));
+
+    ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+        for (idx, eccentricity) in matrix.nodes.iter().zip(&eccentricities) {
+            ui.monospace(format!(
+                "{}: eccentricity {}",
+                graph.try_get_node(*idx).map_or("?", |n| n.get_name()),
+                distance_text(*eccentricity)
+            ));
+        }
+    });
+}
@@ -0,0 +1,292 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use egui::{ComboBox, DragValue, Pos2, Ui, Widget};
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{check_no_negative_edges, mark_visited, require_start, Queued, Traverser},
+};
+
+/// How far a node is guessed to be from the end, going by where they are on screen
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum Heuristic {
+    #[default]
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+    Zero,
+}
+
+impl Heuristic {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Heuristic::Euclidean => "Straight Line",
+            Heuristic::Manhattan => "Manhattan",
+            Heuristic::Chebyshev => "Chebyshev",
+            Heuristic::Zero => "Zero (Dijkstra's)",
+        }
+    }
+
+    pub const fn values() -> [Heuristic; 4] {
+        [
+            Heuristic::Euclidean,
+            Heuristic::Manhattan,
+            Heuristic::Chebyshev,
+            Heuristic::Zero,
+        ]
+    }
+
+    pub fn estimate(&self, from: Pos2, to: Pos2) -> f32 {
+        let (dx, dy) = ((to.x - from.x).abs(), (to.y - from.y).abs());
+
+        match self {
+            Heuristic::Euclidean => from.distance(to),
+            Heuristic::Manhattan => dx + dy,
+            Heuristic::Chebyshev => dx.max(dy),
+            Heuristic::Zero => 0.0,
+        }
+    }
+}
+
+/// Shows a combo box to pick a heuristic
+pub fn heuristic_ui(ui: &mut Ui, heuristic: &mut Heuristic) {
+    let heuristics = Heuristic::values();
+    let mut curr_heuristic = heuristics.iter().position(|h| h == heuristic).unwrap();
+
+    ComboBox::from_label("Heuristic").show_index(ui, &mut curr_heuristic, heuristics.len(), |i| {
+        heuristics[i].name().to_owned()
+    });
+
+    *heuristic = heuristics[curr_heuristic];
+}
+
+/// The smallest edge weight per pixel of edge length, so a distance on screen times this is
+/// never more than the weight of any path covering it
+fn heuristic_scale(graph: &Graph) -> f32 {
+    let scale = graph
+        .get_edges()
+        .iter()
+        .filter_map(|edge| {
+            let (weight, a, b) = edge.get_weighted_nodes();
+            let a: Pos2 = graph.get_node(a).get_pos().into();
+            let length = a.distance(graph.get_node(b).get_pos().into());
+            (length > 0.0).then(|| weight / length)
+        })
+        .fold(f32::INFINITY, f32::min);
+
+    // Without any edges there's nothing to guess from, and nothing to reach either
+    if scale.is_finite() {
+        scale.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+/// Shows a node's distance so far (g), guess at the distance left (h) and their total (f)
+fn score_badge(g: f32, h: f32) -> String {
+    format!("g {g:.1} h {h:.1} f {:.1}", g + h)
+}
+
+/// Like Dijkstra's but the queue is ordered by f, the distance so far (g) plus a guess at the
+/// distance left to the end node (h)
+///
+/// Distances on screen are turned into edge weight units by the smallest weight per pixel of any
+/// edge, so the straight line and Chebyshev guesses are never more than the real distance left
+/// and a weight of 1 always finds the shortest path. Manhattan can guess up to about 1.4 times
+/// too much unless the edges only run across and down. Weights above 1 head for the end more
+/// eagerly but may miss the shortest path. Each node's badge shows its g, h and f.
+#[derive(Clone)]
+pub struct AStar {
+    heuristic: Heuristic,
+    weight: f32,
+    /// How much edge weight each pixel on screen is worth
+    scale: f32,
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+    end_pos: Pos2,
+    queue: BinaryHeap<Queued>,
+    /// The shortest distance found to each node so far
+    distances: HashMap<NodeIndex, f32>,
+    visited: HashSet<NodeIndex>,
+    /// The g, h and f of the node visited last
+    last_visited: Option<(NodeIndex, f32, f32)>,
+    found: bool,
+}
+
+impl Default for AStar {
+    fn default() -> Self {
+        AStar {
+            heuristic: Heuristic::default(),
+            weight: 1.0,
+            scale: 1.0,
+            start: None,
+            end: None,
+            end_pos: Pos2::ZERO,
+            queue: BinaryHeap::new(),
+            distances: HashMap::new(),
+            visited: HashSet::new(),
+            last_visited: None,
+            found: false,
+        }
+    }
+}
+
+impl AStar {
+    fn skip_visited(&mut self) {
+        while matches!(self.queue.peek(), Some(q) if self.visited.contains(&q.node)) {
+            self.queue.pop();
+        }
+    }
+
+    /// The scaled guess at how far a node is from the end
+    fn remaining(&self, graph: &Graph, idx: NodeIndex) -> f32 {
+        self.weight
+            * self.scale
+            * self
+                .heuristic
+                .estimate(graph.get_node(idx).get_pos().into(), self.end_pos)
+    }
+}
+
+impl Traverser for AStar {
+    fn name(&self) -> &'static str {
+        "A*"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        let Some(end) = end else {
+            return Err("A* needs an end node to head towards".to_owned());
+        };
+        check_no_negative_edges(graph, "A*")?;
+
+        *self = AStar {
+            heuristic: self.heuristic,
+            weight: self.weight,
+            scale: heuristic_scale(graph),
+            start: Some(start),
+            end: Some(end),
+            end_pos: graph.get_node(end).get_pos().into(),
+            distances: HashMap::from([(start, 0.0)]),
+            ..Default::default()
+        };
+        self.queue.push(Queued {
+            priority: self.remaining(graph, start),
+            node: start,
+        });
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(Queued {
+            node: idx,
+            priority,
+        }) = self.queue.pop()
+        else {
+            return;
+        };
+
+        mark_visited(graph, idx, self.start, self.end);
+        self.visited.insert(idx);
+
+        let distance = self.distances[&idx];
+        self.last_visited = Some((idx, distance, priority - distance));
+        graph
+            .get_node_mut(idx)
+            .set_badge(Some(score_badge(distance, priority - distance)));
+
+        if Some(idx) == self.end {
+            self.found = true;
+        } else {
+            for edge in graph.get_node(idx).get_edges() {
+                let (weight, _, b) = edge.get_weighted_nodes();
+
+                if self.visited.contains(&b) {
+                    continue;
+                }
+
+                let new_distance = distance + weight;
+                let remaining = self.remaining(graph, b);
+
+                let node_to_visit = graph.get_node_mut(b);
+                node_to_visit.view();
+
+                if new_distance < self.distances.get(&b).copied().unwrap_or(f32::INFINITY) {
+                    self.distances.insert(b, new_distance);
+                    node_to_visit.set_last_node(idx, new_distance);
+                    node_to_visit.set_badge(Some(score_badge(new_distance, remaining)));
+
+                    self.queue.push(Queued {
+                        priority: new_distance + remaining,
+                        node: b,
+                    });
+                }
+            }
+        }
+
+        self.skip_visited();
+    }
+
+    fn finished(&self) -> bool {
+        self.found || self.queue.is_empty()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        let mut queue = self.queue.clone().into_sorted_vec();
+        // The heap is ordered backwards so the sorted queue is too
+        queue.reverse();
+
+        let mut seen = HashSet::new();
+        queue
+            .into_iter()
+            .map(|q| q.node)
+            .filter(|n| !self.visited.contains(n) && seen.insert(*n))
+            .collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.iter().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn settings_ui(&mut self, ui: &mut Ui) {
+        heuristic_ui(ui, &mut self.heuristic);
+
+        if self.heuristic != Heuristic::Zero {
+            ui.horizontal(|ui| {
+                ui.label("Heuristic Weight");
+                DragValue::new(&mut self.weight)
+                    .clamp_range(0.0..=10.0)
+                    .speed(0.05)
+                    .ui(ui);
+            });
+        }
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        if let Some((idx, g, h)) = self.last_visited {
+            ui.label(format!(
+                "Visited {} with g {g:.1}, h {h:.1}, f {:.1}",
+                graph.get_node(idx).get_name(),
+                g + h
+            ));
+        }
+
+        if self.found {
+            if let Some(distance) = self.end.and_then(|end| self.distances.get(&end)) {
+                ui.label(format!("Found a path of length {distance}"));
+            }
+        } else if self.finished() {
+            ui.label("The end can't be reached from the start");
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use egui::Ui;
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex},
+    traversers::{
+        coloring::{adjacency, color_node, coloring_ui, largest_first},
+        Traverser,
+    },
+};
+
+/// Any more nodes than this and there could be far too many steps to go through
+const MAX_NODES: usize = 12;
+
+/// What the last step did
+#[derive(Clone)]
+enum Attempt {
+    Colored(NodeIndex, usize),
+    Clashed(NodeIndex, usize, Vec<NodeIndex>),
+    BackedUp(NodeIndex),
+    NotEnough(usize),
+}
+
+/// Finds the fewest colors a graph can be colored with by trying every way of coloring it
+///
+/// Starting with one color, nodes are given colors one at a time, backing up to the last node
+/// whenever one has nothing left to try. If it backs up past the first node there's no way to
+/// do it and it starts again with one more color. A node is never given a color more than one
+/// past the highest used so far, since swapping colors around would give the same coloring.
+#[derive(Clone, Default)]
+pub struct BacktrackingColoring {
+    order: Vec<NodeIndex>,
+    adjacency: HashMap<NodeIndex, Vec<NodeIndex>>,
+    colors: HashMap<NodeIndex, usize>,
+    /// How many colors we're trying to use
+    color_limit: usize,
+    /// How many of the nodes in `order` have been colored
+    next: usize,
+    /// The next color to try for each node
+    next_color: Vec<usize>,
+    last_attempt: Option<Attempt>,
+    /// The edges marked as clashing last step
+    marked: Vec<(NodeIndex, NodeIndex)>,
+}
+
+impl BacktrackingColoring {
+    fn unmark(&mut self, graph: &mut Graph) {
+        for (a, b) in self.marked.drain(..) {
+            graph.set_edge_state(a, b, EdgeState::None);
+        }
+    }
+
+    /// Takes the color off a node that's being backed out of
+    fn uncolor(&mut self, graph: &mut Graph, idx: NodeIndex) {
+        self.colors.remove(&idx);
+
+        let node = graph.get_node_mut(idx);
+        node.view();
+        node.set_group(None);
+    }
+}
+
+impl Traverser for BacktrackingColoring {
+    fn name(&self) -> &'static str {
+        "Backtracking Coloring"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        if graph.get_nodes().len() > MAX_NODES {
+            return Err(format!(
+                "Backtracking could take far too long on more than {MAX_NODES} nodes, try DSatur \
+                 instead"
+            ));
+        }
+
+        let adjacency = adjacency(graph);
+        let order = largest_first(graph, &adjacency);
+
+        *self = BacktrackingColoring {
+            next_color: vec![0; order.len()],
+            order,
+            adjacency,
+            color_limit: 1,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        self.unmark(graph);
+
+        let Some(idx) = self.order.get(self.next).copied() else {
+            return;
+        };
+
+        let color = self.next_color[self.next];
+        let highest = self.order[..self.next]
+            .iter()
+            .map(|n| self.colors[n] + 1)
+            .max()
+            .unwrap_or_default();
+
+        if color >= self.color_limit.min(highest + 1) {
+            self.next_color[self.next] = 0;
+            self.uncolor(graph, idx);
+
+            if self.next == 0 {
+                self.last_attempt = Some(Attempt::NotEnough(self.color_limit));
+                self.color_limit += 1;
+                return;
+            }
+
+            self.next -= 1;
+            let prev = self.order[self.next];
+            self.next_color[self.next] += 1;
+            self.uncolor(graph, prev);
+            self.last_attempt = Some(Attempt::BackedUp(prev));
+            return;
+        }
+
+        let clashes = self.adjacency[&idx]
+            .iter()
+            .filter(|n| self.colors.get(n) == Some(&color))
+            .copied()
+            .collect::<Vec<_>>();
+
+        let node = graph.get_node_mut(idx);
+        node.view();
+        node.set_group(Some(color));
+
+        if clashes.is_empty() {
+            self.colors.insert(idx, color);
+            color_node(graph, idx, color);
+            self.next += 1;
+            self.last_attempt = Some(Attempt::Colored(idx, color));
+        } else {
+            for n in &clashes {
+                graph.set_edge_state(idx, *n, EdgeState::Conflict);
+                self.marked.push((idx, *n));
+            }
+
+            self.next_color[self.next] += 1;
+            self.last_attempt = Some(Attempt::Clashed(idx, color, clashes));
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.next >= self.order.len()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.order[self.next..].to_vec()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.order[..self.next].to_vec()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        let name = |idx: &NodeIndex| graph.get_node(*idx).get_name();
+
+        if self.finished() {
+            ui.label(format!(
+                "This graph needs {} colors, there's no way to use fewer",
+                self.color_limit
+            ));
+        } else {
+            ui.label(format!("Trying to use {} colors", self.color_limit));
+        }
+
+        match &self.last_attempt {
+            Some(Attempt::Colored(idx, color)) => {
+                ui.label(format!("Gave {} color {}", name(idx), color + 1));
+            }
+            Some(Attempt::Clashed(idx, color, clashes)) => {
+                ui.label(format!(
+                    "{} can't have color {}, {} already has it",
+                    name(idx),
+                    color + 1,
+                    clashes.iter().map(name).collect::<Vec<_>>().join(", ")
+                ));
+            }
+            Some(Attempt::BackedUp(idx)) => {
+                ui.label(format!("Nothing left to try, backing up to {}", name(idx)));
+            }
+            Some(Attempt::NotEnough(limit)) => {
+                ui.label(format!("There's no way to use only {limit} colors"));
+            }
+            None => {}
+        }
+
+        coloring_ui(ui, graph, &self.colors, self.finished());
+    }
+}
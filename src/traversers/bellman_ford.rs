@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+
+use egui::Ui;
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex},
+    traversers::{cycle_names, mark_cycle, mark_visited, require_start, Traverser},
+};
+
+#[derive(Clone)]
+enum Outcome {
+    ShortestPaths,
+    /// The nodes of the cycle in order, along with its total weight
+    NegativeCycle(Vec<NodeIndex>, f32),
+}
+
+/// Finds shortest paths even with negative weights, one relaxation pass per step
+///
+/// If anything can still be relaxed after every node has had its chance to be on a path
+/// there has to be a negative cycle, which gets painted instead.
+#[derive(Clone, Default)]
+pub struct BellmanFord {
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+    node_count: usize,
+    passes: usize,
+    distances: HashMap<NodeIndex, f32>,
+    predecessors: HashMap<NodeIndex, NodeIndex>,
+    /// The edges relaxed during the last pass
+    relaxed: Vec<(NodeIndex, NodeIndex)>,
+    outcome: Option<Outcome>,
+}
+
+impl BellmanFord {
+    /// Follows predecessors back from a node that could still be relaxed to find the cycle
+    fn find_cycle(&self, graph: &Graph, from: NodeIndex) -> (Vec<NodeIndex>, f32) {
+        // Going back once for every node guarantees we end up on the cycle itself
+        let mut on_cycle = from;
+        for _ in 0..self.node_count {
+            on_cycle = self.predecessors[&on_cycle];
+        }
+
+        let mut cycle = vec![on_cycle];
+        let mut curr = self.predecessors[&on_cycle];
+        while curr != on_cycle {
+            cycle.push(curr);
+            curr = self.predecessors[&curr];
+        }
+        cycle.reverse();
+
+        let weight = cycle
+            .iter()
+            .map(|n| {
+                let pred = self.predecessors[n];
+                graph
+                    .get_node(pred)
+                    .get_edges()
+                    .iter()
+                    .filter(|e| e.get_nodes().1 == *n)
+                    .map(|e| e.get_weight())
+                    .fold(f32::INFINITY, f32::min)
+            })
+            .sum();
+
+        (cycle, weight)
+    }
+}
+
+impl Traverser for BellmanFord {
+    fn name(&self) -> &'static str {
+        "Bellman-Ford"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        *self = BellmanFord {
+            start: Some(start),
+            end,
+            node_count: graph.get_nodes().len(),
+            distances: HashMap::from([(start, 0.0)]),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(start) = self.start else {
+            return;
+        };
+
+        if self.passes == 0 {
+            graph.get_node_mut(start).start();
+        }
+
+        for (a, b) in self.relaxed.drain(..) {
+            graph.set_edge_state(a, b, EdgeState::None);
+        }
+
+        self.passes += 1;
+
+        let edges = graph
+            .get_nodes()
+            .iter()
+            .flat_map(|n| n.get_edges())
+            .collect::<Vec<_>>();
+
+        for edge in edges {
+            let (weight, a, b) = edge.get_weighted_nodes();
+
+            let Some(distance) = self.distances.get(&a).map(|d| d + weight) else {
+                continue;
+            };
+
+            if distance >= self.distances.get(&b).copied().unwrap_or(f32::INFINITY) {
+                continue;
+            }
+
+            // Every shortest path has been found by now, so this edge must be on a negative cycle
+            if self.passes >= self.node_count {
+                self.predecessors.insert(b, a);
+                let (cycle, weight) = self.find_cycle(graph, b);
+
+                mark_cycle(graph, &cycle);
+
+                self.outcome = Some(Outcome::NegativeCycle(cycle, weight));
+                return;
+            }
+
+            self.distances.insert(b, distance);
+            self.predecessors.insert(b, a);
+            self.relaxed.push((a, b));
+
+            let node = graph.get_node_mut(b);
+            if b != start {
+                node.view();
+                node.set_last_node(a, distance);
+            }
+            graph.set_edge_state(a, b, EdgeState::Relaxed);
+        }
+
+        if self.relaxed.is_empty() {
+            for idx in self.distances.keys() {
+                mark_visited(graph, *idx, self.start, self.end);
+            }
+
+            self.outcome = Some(Outcome::ShortestPaths);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        let mut frontier = Vec::new();
+        for (_, b) in &self.relaxed {
+            if !frontier.contains(b) {
+                frontier.push(*b);
+            }
+        }
+        frontier
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.distances.keys().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn shows_path(&self) -> bool {
+        !matches!(self.outcome, Some(Outcome::NegativeCycle(..)))
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        match &self.outcome {
+            None => {
+                ui.label(format!(
+                    "Pass {} of at most {}, relaxed {} edges",
+                    self.passes,
+                    self.node_count.saturating_sub(1),
+                    self.relaxed.len()
+                ));
+            }
+            Some(Outcome::ShortestPaths) => {
+                ui.label(format!(
+                    "Found every shortest path after {} passes",
+                    self.passes
+                ));
+
+                if let Some(end) = self.end {
+                    let name = graph.get_node(end).get_name();
+                    match self.distances.get(&end) {
+                        Some(distance) => ui.label(format!("Distance to {name}: {distance}")),
+                        None => ui.label(format!("{name} can't be reached")),
+                    };
+                }
+            }
+            Some(Outcome::NegativeCycle(cycle, weight)) => {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    format!(
+                        "Found a negative cycle with total weight {weight}: {}",
+                        cycle_names(graph, cycle)
+                    ),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::{HashMap, VecDeque};
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{mark_visited, require_start, Traverser},
+};
+
+/// Which way a side of the search goes along edges
+const FORWARD: usize = 0;
+const BACKWARD: usize = 1;
+
+/// Searches breadth first from the start and the end at once until the two searches meet
+///
+/// Each step expands a node, and each level of a search is expanded all together by whichever
+/// side has the smaller frontier when the level starts. Once the sides touch the rest of the
+/// level is finished off in case it has a shorter way across. Nodes are painted in the group
+/// color of the side that found them, and the search from the end follows edges backwards so
+/// it finds nodes that can reach the end.
+#[derive(Clone, Default)]
+pub struct BidirectionalSearch {
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+    queues: [VecDeque<NodeIndex>; 2],
+    /// The node each side found each node from, the start and end are their own parents
+    parents: [HashMap<NodeIndex, NodeIndex>; 2],
+    /// How many edges each node is from where each side started
+    depths: [HashMap<NodeIndex, usize>; 2],
+    /// The side and level being expanded
+    level: Option<(usize, usize)>,
+    /// The shortest way across found so far, as its length and the edge between the sides
+    best: Option<(usize, NodeIndex, NodeIndex)>,
+    /// The nodes whose edges come into each node
+    incoming: HashMap<NodeIndex, Vec<NodeIndex>>,
+    visited: Vec<NodeIndex>,
+    expanded: [usize; 2],
+    /// The path from the start to the end once the sides have met
+    path: Option<Vec<NodeIndex>>,
+}
+
+impl BidirectionalSearch {
+    /// Follows a side's parents back to where it started
+    fn chain(&self, side: usize, mut idx: NodeIndex) -> Vec<NodeIndex> {
+        let mut chain = vec![idx];
+        while let Some(parent) = self.parents[side].get(&idx).filter(|p| **p != idx) {
+            idx = *parent;
+            chain.push(idx);
+        }
+        chain
+    }
+
+    /// How deep the next node a side will expand is
+    fn front_depth(&self, side: usize) -> Option<usize> {
+        let idx = self.queues[side].front()?;
+        self.depths[side].get(idx).copied()
+    }
+
+    /// Joins the two sides across the edge from `a` to `b`, pointing every node on the path
+    /// back towards the start so it gets drawn
+    fn meet(&mut self, graph: &mut Graph, a: NodeIndex, b: NodeIndex) {
+        let mut path = self.chain(FORWARD, a);
+        path.reverse();
+        path.extend(self.chain(BACKWARD, b));
+
+        for (i, pair) in path.windows(2).enumerate() {
+            graph
+                .get_node_mut(pair[1])
+                .set_last_node(pair[0], (i + 1) as f32);
+        }
+
+        self.path = Some(path);
+    }
+}
+
+impl Traverser for BidirectionalSearch {
+    fn name(&self) -> &'static str {
+        "Bidirectional Search"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        let Some(end) = end else {
+            return Err("Bidirectional search needs an end to search back from".to_owned());
+        };
+
+        let mut incoming = HashMap::<_, Vec<_>>::new();
+        for node in graph.get_nodes() {
+            for n in node.get_neighbors() {
+                incoming.entry(n).or_default().push(node.get_id());
+            }
+        }
+
+        *self = BidirectionalSearch {
+            start: Some(start),
+            end: Some(end),
+            queues: [VecDeque::from([start]), VecDeque::from([end])],
+            parents: [HashMap::from([(start, start)]), HashMap::from([(end, end)])],
+            depths: [HashMap::from([(start, 0)]), HashMap::from([(end, 0)])],
+            incoming,
+            path: (start == end).then(|| vec![start]),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        if self.finished() {
+            return;
+        }
+
+        let side = match self.level {
+            Some((side, level)) if self.front_depth(side) == Some(level) => side,
+            _ => {
+                // Nothing on the other side of the level can be any shorter
+                if let Some((_, a, b)) = self.best {
+                    self.meet(graph, a, b);
+                    return;
+                }
+
+                let side = if self.queues[FORWARD].len() <= self.queues[BACKWARD].len() {
+                    FORWARD
+                } else {
+                    BACKWARD
+                };
+                self.level = self.front_depth(side).map(|l| (side, l));
+                side
+            }
+        };
+
+        let Some(idx) = self.queues[side].pop_front() else {
+            return;
+        };
+        let depth = self.depths[side][&idx];
+
+        mark_visited(graph, idx, self.start, self.end);
+        graph.get_node_mut(idx).set_group(Some(side));
+        self.visited.push(idx);
+        self.expanded[side] += 1;
+
+        let neighbors = if side == FORWARD {
+            graph.get_node(idx).get_neighbors()
+        } else {
+            self.incoming.get(&idx).cloned().unwrap_or_default()
+        };
+
+        for n in neighbors {
+            if self.parents[side].contains_key(&n) {
+                continue;
+            }
+            self.parents[side].insert(n, idx);
+            self.depths[side].insert(n, depth + 1);
+
+            if let Some(other_depth) = self.depths[1 - side].get(&n) {
+                let length = depth + 1 + other_depth;
+
+                if !matches!(self.best, Some((best, ..)) if best <= length) {
+                    self.best = Some(match side {
+                        FORWARD => (length, idx, n),
+                        _ => (length, n, idx),
+                    });
+                }
+                continue;
+            }
+
+            let node = graph.get_node_mut(n);
+            node.view();
+            node.set_group(Some(side));
+            self.queues[side].push_back(n);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.path.is_some() || (self.best.is_none() && self.queues.iter().any(|q| q.is_empty()))
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.queues.iter().flatten().copied().collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.clone()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn results_ui(&self, _graph: &Graph, ui: &mut Ui) {
+        ui.label(format!(
+            "Expanded {} nodes from the start and {} from the end",
+            self.expanded[FORWARD], self.expanded[BACKWARD]
+        ));
+
+        match &self.path {
+            Some(path) => {
+                ui.label(format!(
+                    "The searches met on a path with {} edges",
+                    path.len() - 1
+                ));
+            }
+            None if self.finished() => {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    "The searches never met, so there's no path",
+                );
+            }
+            None => {}
+        }
+    }
+}
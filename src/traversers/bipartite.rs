@@ -0,0 +1,199 @@
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{check_undirected, cycle_names, mark_cycle, Traverser},
+};
+
+/// Splits a graph into two sides with no edges inside either side, if it can be
+///
+/// Nodes are painted in the group color of their side, which is the opposite of whichever
+/// node found them. If an edge joins two nodes on the same side, going up the search tree
+/// from both ends gives a cycle of odd length, which proves it can't be done.
+#[derive(Clone, Default)]
+pub struct BipartiteCheck {
+    /// Every node, in the order we try to start searches from
+    roots: Vec<NodeIndex>,
+    next_root: usize,
+    queue: VecDeque<NodeIndex>,
+    sides: HashMap<NodeIndex, usize>,
+    parents: HashMap<NodeIndex, NodeIndex>,
+    visited: Vec<NodeIndex>,
+    odd_cycle: Option<Vec<NodeIndex>>,
+}
+
+impl BipartiteCheck {
+    fn path_to_root(&self, mut idx: NodeIndex) -> Vec<NodeIndex> {
+        let mut path = vec![idx];
+        while let Some(parent) = self.parents.get(&idx) {
+            idx = *parent;
+            path.push(idx);
+        }
+        path
+    }
+
+    /// Joins up the paths from `a` and `b` back to where they meet
+    fn find_cycle(&self, a: NodeIndex, b: NodeIndex) -> Vec<NodeIndex> {
+        let from_a = self.path_to_root(a);
+        let from_b = self.path_to_root(b);
+
+        let (meet_a, meet_b) = from_a
+            .iter()
+            .enumerate()
+            .find_map(|(i, n)| Some((i, from_b.iter().position(|m| m == n)?)))
+            .unwrap_or((from_a.len() - 1, from_b.len() - 1));
+
+        let mut cycle = from_a[..=meet_a].to_vec();
+        cycle.extend(from_b[..meet_b].iter().rev());
+        cycle
+    }
+}
+
+/// Splits a graph into two sides without animating anything, or returns none if there's an
+/// edge that would have to be inside a side
+pub fn two_coloring(graph: &Graph) -> Option<HashMap<NodeIndex, usize>> {
+    let mut sides = HashMap::new();
+
+    for root in graph.get_nodes().iter().map(|n| n.get_id()) {
+        if sides.contains_key(&root) {
+            continue;
+        }
+
+        sides.insert(root, 0);
+        let mut queue = VecDeque::from([root]);
+
+        while let Some(idx) = queue.pop_front() {
+            let side = sides[&idx];
+
+            for n in graph.get_node(idx).get_neighbors() {
+                match sides.get(&n) {
+                    None => {
+                        sides.insert(n, 1 - side);
+                        queue.push_back(n);
+                    }
+                    Some(s) if *s == side => return None,
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    Some(sides)
+}
+
+impl Traverser for BipartiteCheck {
+    fn name(&self) -> &'static str {
+        "Bipartite Check"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_undirected(graph, "The bipartite check")?;
+
+        *self = BipartiteCheck {
+            roots: graph.get_nodes().iter().map(|n| n.get_id()).collect(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(idx) = self.queue.pop_front() else {
+            while let Some(root) = self.roots.get(self.next_root).copied() {
+                self.next_root += 1;
+
+                if let Entry::Vacant(entry) = self.sides.entry(root) {
+                    entry.insert(0);
+                    self.queue.push_back(root);
+                    graph.get_node_mut(root).view();
+                    return;
+                }
+            }
+            return;
+        };
+
+        let side = self.sides[&idx];
+        let node = graph.get_node_mut(idx);
+        node.visit();
+        node.set_group(Some(side));
+        self.visited.push(idx);
+
+        for n in graph.get_node(idx).get_neighbors() {
+            match self.sides.get(&n) {
+                None => {
+                    self.sides.insert(n, 1 - side);
+                    self.parents.insert(n, idx);
+                    self.queue.push_back(n);
+                    graph.get_node_mut(n).view();
+                }
+                Some(s) if *s == side => {
+                    let cycle = self.find_cycle(idx, n);
+                    mark_cycle(graph, &cycle);
+                    self.odd_cycle = Some(cycle);
+                    self.queue.clear();
+                    return;
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.odd_cycle.is_some()
+            || (self.queue.is_empty()
+                && self.roots[self.next_root..]
+                    .iter()
+                    .all(|n| self.sides.contains_key(n)))
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.queue.iter().copied().collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.clone()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        if let Some(cycle) = &self.odd_cycle {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!(
+                    "Not bipartite, the cycle {} has an odd number of edges",
+                    cycle_names(graph, cycle)
+                ),
+            );
+        } else if self.finished() {
+            let left = self.sides.values().filter(|s| **s == 0).count();
+            ui.label(format!(
+                "Bipartite, split into sides of {left} and {} nodes",
+                self.sides.len() - left
+            ));
+        } else {
+            ui.label(format!("Colored {} nodes so far", self.visited.len()));
+        }
+    }
+}
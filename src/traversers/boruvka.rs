@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use egui::Ui;
+
+use crate::{
+    graph::{Edge, EdgeState, Graph, NodeIndex},
+    traversers::{check_undirected, tree_weight_ui, Traverser, UnionFind},
+};
+
+/// Every tree picks the lightest edge leaving it at the same time, one round per step
+#[derive(Clone, Default)]
+pub struct Boruvka {
+    edges: Vec<Edge>,
+    accepted: Vec<bool>,
+    trees: UnionFind,
+    nodes: Vec<NodeIndex>,
+    rounds: usize,
+    tree_weight: f32,
+    tree_edges: usize,
+    done: bool,
+}
+
+impl Boruvka {
+    fn tree_count(&self) -> usize {
+        self.nodes
+            .iter()
+            .filter(|n| self.trees.find(**n) == **n)
+            .count()
+    }
+}
+
+impl Traverser for Boruvka {
+    fn name(&self) -> &'static str {
+        "Borůvka's Minimum Spanning Tree"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_undirected(graph, "Borůvka's")?;
+
+        let edges = graph.get_edges();
+
+        *self = Boruvka {
+            accepted: vec![false; edges.len()],
+            edges,
+            nodes: graph.get_nodes().iter().map(|n| n.get_id()).collect(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        // The lightest edge out of each tree, ties are broken by position so no cycles get made
+        let mut cheapest: HashMap<NodeIndex, usize> = HashMap::new();
+
+        for (i, edge) in self.edges.iter().enumerate() {
+            let (weight, a, b) = edge.get_weighted_nodes();
+            let (tree_a, tree_b) = (self.trees.find(a), self.trees.find(b));
+
+            if tree_a == tree_b {
+                continue;
+            }
+
+            for tree in [tree_a, tree_b] {
+                let is_lighter = match cheapest.get(&tree) {
+                    Some(j) => weight < self.edges[*j].get_weight(),
+                    None => true,
+                };
+
+                if is_lighter {
+                    cheapest.insert(tree, i);
+                }
+            }
+        }
+
+        if cheapest.is_empty() {
+            self.done = true;
+            return;
+        }
+
+        self.rounds += 1;
+
+        let mut chosen = cheapest.into_values().collect::<Vec<_>>();
+        chosen.sort_unstable();
+        chosen.dedup();
+
+        for i in chosen {
+            let (weight, a, b) = self.edges[i].get_weighted_nodes();
+
+            if self.trees.union(a, b) {
+                self.accepted[i] = true;
+                graph.set_single_edge_state(a, b, weight, EdgeState::Accepted);
+                graph.get_node_mut(a).visit();
+                graph.get_node_mut(b).visit();
+
+                self.tree_weight += weight;
+                self.tree_edges += 1;
+            }
+        }
+
+        // Anything that now sits inside a single tree will never be picked
+        for (i, edge) in self.edges.iter().enumerate() {
+            let (weight, a, b) = edge.get_weighted_nodes();
+
+            if !self.accepted[i] && self.trees.find(a) == self.trees.find(b) {
+                graph.set_single_edge_state(a, b, weight, EdgeState::Rejected);
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.done
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        Vec::new()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.edges
+            .iter()
+            .zip(&self.accepted)
+            .filter(|(_, accepted)| **accepted)
+            .flat_map(|(e, _)| {
+                let (a, b) = e.get_nodes();
+                [a, b]
+            })
+            .collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, _graph: &Graph, ui: &mut Ui) {
+        tree_weight_ui(ui, self.tree_weight, self.tree_edges, self.finished());
+        ui.label(format!(
+            "Round {}, {} trees left",
+            self.rounds,
+            self.tree_count()
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{mark_visited, require_start, Traverser},
+};
+
+#[derive(Clone, Default)]
+pub struct BreadthFirst {
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+    queue: VecDeque<NodeIndex>,
+    visited: HashSet<NodeIndex>,
+}
+
+impl Traverser for BreadthFirst {
+    fn name(&self) -> &'static str {
+        "Breadth First Search"
+    }
+
+    fn init(
+        &mut self,
+        _graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        *self = BreadthFirst {
+            start: Some(start),
+            end,
+            queue: VecDeque::from([start]),
+            visited: HashSet::new(),
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(idx) = self.queue.pop_front() else {
+            return;
+        };
+
+        mark_visited(graph, idx, self.start, self.end);
+        self.visited.insert(idx);
+
+        if Some(idx) == self.end {
+            return;
+        }
+
+        let node = graph.get_node(idx);
+        let path_len = if Some(idx) == self.start {
+            0.0
+        } else {
+            node.get_curr_path()
+        };
+
+        for n in node.get_neighbors() {
+            if !self.visited.contains(&n) && !self.queue.contains(&n) {
+                let node_to_visit = graph.get_node_mut(n);
+                node_to_visit.view();
+                node_to_visit.set_last_node(idx, path_len + 1.0);
+
+                self.queue.push_back(n);
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.queue.iter().copied().collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.iter().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use egui::{ScrollArea, Ui};
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex},
+    traversers::{check_undirected, Traverser},
+};
+
+/// A node being searched
+#[derive(Clone, Copy)]
+struct Frame {
+    node: NodeIndex,
+    parent: Option<NodeIndex>,
+    /// How many neighbors have been looked at
+    followed: usize,
+    /// Whether we've skipped over the edge back to the parent, any other edge back to it is
+    /// a second way there
+    skipped_parent: bool,
+    children: usize,
+}
+
+/// Finds the edges and nodes that would disconnect the graph if they were removed
+///
+/// Each node shows the order it was discovered in and the lowest discovery number it can get
+/// back to without going through its parent. If a child can't get back above its parent,
+/// the parent is an articulation point, and if it can't even get back to the parent the
+/// edge between them is a bridge.
+#[derive(Clone, Default)]
+pub struct Bridges {
+    /// Every node, in the order we try to start searches from
+    roots: Vec<NodeIndex>,
+    next_root: usize,
+    stack: Vec<Frame>,
+    discovered: HashMap<NodeIndex, usize>,
+    low_links: HashMap<NodeIndex, usize>,
+    bridges: Vec<(NodeIndex, NodeIndex)>,
+    articulation_points: Vec<NodeIndex>,
+}
+
+impl Bridges {
+    fn discover(&mut self, graph: &mut Graph, idx: NodeIndex, parent: Option<NodeIndex>) {
+        let number = self.discovered.len();
+        self.discovered.insert(idx, number);
+        self.low_links.insert(idx, number);
+        self.stack.push(Frame {
+            node: idx,
+            parent,
+            followed: 0,
+            skipped_parent: false,
+            children: 0,
+        });
+
+        graph.get_node_mut(idx).view();
+        self.set_badge(graph, idx);
+    }
+
+    fn lower_link(&mut self, graph: &mut Graph, idx: NodeIndex, to: usize) {
+        if let Some(low) = self.low_links.get_mut(&idx) {
+            if to < *low {
+                *low = to;
+                self.set_badge(graph, idx);
+            }
+        }
+    }
+
+    fn set_badge(&self, graph: &mut Graph, idx: NodeIndex) {
+        graph.get_node_mut(idx).set_badge(Some(format!(
+            "{}/{}",
+            self.discovered[&idx], self.low_links[&idx]
+        )));
+    }
+
+    fn mark_articulation_point(&mut self, graph: &mut Graph, idx: NodeIndex) {
+        if !self.articulation_points.contains(&idx) {
+            self.articulation_points.push(idx);
+            graph.get_node_mut(idx).set_ring(true);
+        }
+    }
+}
+
+impl Traverser for Bridges {
+    fn name(&self) -> &'static str {
+        "Bridges and Articulation Points"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_undirected(graph, "Finding bridges")?;
+
+        *self = Bridges {
+            roots: graph.get_nodes().iter().map(|n| n.get_id()).collect(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(mut frame) = self.stack.last().copied() else {
+            while let Some(root) = self.roots.get(self.next_root).copied() {
+                self.next_root += 1;
+
+                if !self.discovered.contains_key(&root) {
+                    self.discover(graph, root, None);
+                    return;
+                }
+            }
+            return;
+        };
+
+        let neighbors = graph.get_node(frame.node).get_neighbors();
+
+        // Edges back to nodes we've already found only update the low link, so they don't get
+        // a step of their own
+        let next = loop {
+            let Some(next) = neighbors.get(frame.followed).copied() else {
+                break None;
+            };
+            frame.followed += 1;
+
+            if Some(next) == frame.parent && !frame.skipped_parent {
+                frame.skipped_parent = true;
+                continue;
+            }
+
+            match self.discovered.get(&next) {
+                None => break Some(next),
+                Some(number) => self.lower_link(graph, frame.node, *number),
+            }
+        };
+
+        if let Some(next) = next {
+            frame.children += 1;
+            if let Some(top) = self.stack.last_mut() {
+                *top = frame;
+            }
+
+            self.discover(graph, next, Some(frame.node));
+            return;
+        }
+
+        self.stack.pop();
+        graph.get_node_mut(frame.node).visit();
+
+        // Nothing is above a root, so it only splits the graph if it has more than one subtree
+        if frame.parent.is_none() && frame.children > 1 {
+            self.mark_articulation_point(graph, frame.node);
+        }
+
+        let Some(parent) = frame.parent else {
+            return;
+        };
+
+        let low = self.low_links[&frame.node];
+        self.lower_link(graph, parent, low);
+
+        let parent_number = self.discovered[&parent];
+
+        if low > parent_number {
+            self.bridges.push((parent, frame.node));
+            graph.set_edge_state(parent, frame.node, EdgeState::Bridge);
+        }
+
+        let parent_is_root = matches!(self.stack.last(), Some(f) if f.parent.is_none());
+        if low >= parent_number && !parent_is_root {
+            self.mark_articulation_point(graph, parent);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.stack.is_empty()
+            && self.roots[self.next_root..]
+                .iter()
+                .all(|n| self.discovered.contains_key(n))
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.stack.iter().rev().map(|f| f.node).collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.discovered.keys().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        let name = |idx: &NodeIndex| graph.get_node(*idx).get_name();
+
+        ui.label(format!(
+            "{} bridges and {} articulation points {}",
+            self.bridges.len(),
+            self.articulation_points.len(),
+            if self.finished() { "found" } else { "so far" }
+        ));
+
+        ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+            for (a, b) in &self.bridges {
+                ui.monospace(format!("Bridge {} - {}", name(a), name(b)));
+            }
+
+            for idx in &self.articulation_points {
+                ui.monospace(format!("Articulation point {}", name(idx)));
+            }
+        });
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::{HashMap, HashSet};
+
+use egui::{ScrollArea, Ui};
+
+use crate::graph::{Graph, NodeIndex};
+
+/// Every node each node shares an edge with, ignoring which way the edge goes since two nodes
+/// joined either way can't share a color
+pub fn adjacency(graph: &Graph) -> HashMap<NodeIndex, Vec<NodeIndex>> {
+    let mut adjacency = graph
+        .get_nodes()
+        .iter()
+        .map(|n| (n.get_id(), Vec::new()))
+        .collect::<HashMap<_, Vec<_>>>();
+
+    for edge in graph.get_edges() {
+        let (a, b) = edge.get_nodes();
+
+        for (from, to) in [(a, b), (b, a)] {
+            let neighbors = adjacency.entry(from).or_default();
+            if !neighbors.contains(&to) {
+                neighbors.push(to);
+            }
+        }
+    }
+
+    adjacency
+}
+
+/// Every node in graph order, with the ones that have the most neighbors first
+pub fn largest_first(
+    graph: &Graph,
+    adjacency: &HashMap<NodeIndex, Vec<NodeIndex>>,
+) -> Vec<NodeIndex> {
+    let mut order = graph
+        .get_nodes()
+        .iter()
+        .map(|n| n.get_id())
+        .collect::<Vec<_>>();
+    order.sort_by_key(|n| std::cmp::Reverse(adjacency[n].len()));
+
+    order
+}
+
+/// The lowest color that none of `neighbors` have been given
+pub fn smallest_free_color(neighbors: &[NodeIndex], colors: &HashMap<NodeIndex, usize>) -> usize {
+    let taken = neighbors
+        .iter()
+        .filter_map(|n| colors.get(n))
+        .collect::<HashSet<_>>();
+
+    (0..).find(|c| !taken.contains(c)).unwrap_or_default()
+}
+
+/// Paints a node in the palette color for `color`
+pub fn color_node(graph: &mut Graph, idx: NodeIndex, color: usize) {
+    let node = graph.get_node_mut(idx);
+    node.visit();
+    node.set_group(Some(color));
+}
+
+/// How many different colors have been handed out
+pub fn color_count(colors: &HashMap<NodeIndex, usize>) -> usize {
+    colors.values().collect::<HashSet<_>>().len()
+}
+
+/// The edges joining two nodes of the same color
+pub fn conflicts(graph: &Graph, colors: &HashMap<NodeIndex, usize>) -> Vec<(NodeIndex, NodeIndex)> {
+    graph
+        .get_edges()
+        .iter()
+        .map(|e| e.get_nodes())
+        .filter(|(a, b)| matches!((colors.get(a), colors.get(b)), (Some(x), Some(y)) if x == y))
+        .collect()
+}
+
+/// Shows how many colors a coloring uses and any edges where it went wrong
+pub fn coloring_ui(ui: &mut Ui, graph: &Graph, colors: &HashMap<NodeIndex, usize>, finished: bool) {
+    let name = |idx: &NodeIndex| graph.get_node(*idx).get_name();
+
+    if finished {
+        ui.label(format!(
+            "Colored {} nodes using {} colors",
+            colors.len(),
+            color_count(colors)
+        ));
+    } else {
+        ui.label(format!(
+            "Colored {} of {} nodes using {} colors so far",
+            colors.len(),
+            graph.get_nodes().len(),
+            color_count(colors)
+        ));
+    }
+
+    let conflicts = conflicts(graph, colors);
+    if conflicts.is_empty() {
+        ui.label("No neighboring nodes share a color");
+        return;
+    }
+
+    ui.colored_label(
+        ui.visuals().error_fg_color,
+        format!("{} edges join nodes of the same color", conflicts.len()),
+    );
+
+    ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+        for (a, b) in &conflicts {
+            ui.monospace(format!("{} - {}", name(a), name(b)));
+        }
+    });
+}
@@ -0,0 +1,89 @@
+use std::collections::HashSet;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{mark_visited, require_start, Traverser},
+};
+
+#[derive(Clone, Default)]
+pub struct DepthFirst {
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+    /// The next node to visit is at the end
+    stack: Vec<NodeIndex>,
+    visited: HashSet<NodeIndex>,
+}
+
+impl Traverser for DepthFirst {
+    fn name(&self) -> &'static str {
+        "Depth First Search"
+    }
+
+    fn init(
+        &mut self,
+        _graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        *self = DepthFirst {
+            start: Some(start),
+            end,
+            stack: vec![start],
+            visited: HashSet::new(),
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(idx) = self.stack.pop() else {
+            return;
+        };
+
+        mark_visited(graph, idx, self.start, self.end);
+        self.visited.insert(idx);
+
+        if Some(idx) == self.end {
+            return;
+        }
+
+        let node = graph.get_node(idx);
+        let path_len = if Some(idx) == self.start {
+            0.0
+        } else {
+            node.get_curr_path()
+        };
+
+        let neighbors = node
+            .get_neighbors()
+            .into_iter()
+            .filter(|n| !self.visited.contains(n) && !self.stack.contains(n))
+            .collect::<Vec<_>>();
+
+        // Pushed backwards so the first neighbor gets visited first
+        for n in neighbors.into_iter().rev() {
+            let node_to_visit = graph.get_node_mut(n);
+            node_to_visit.view();
+            node_to_visit.set_last_node(idx, path_len + 1.0);
+
+            self.stack.push(n);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.stack.iter().rev().copied().collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.iter().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{check_no_negative_edges, mark_visited, require_start, Queued, Traverser},
+};
+
+#[derive(Clone, Default)]
+pub struct Dijkstra {
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+    queue: BinaryHeap<Queued>,
+    /// The shortest distance found to each node so far
+    distances: HashMap<NodeIndex, f32>,
+    visited: HashSet<NodeIndex>,
+}
+
+impl Dijkstra {
+    /// Drops queue entries for nodes we've already visited through a shorter path
+    fn skip_visited(&mut self) {
+        while matches!(self.queue.peek(), Some(q) if self.visited.contains(&q.node)) {
+            self.queue.pop();
+        }
+    }
+}
+
+impl Traverser for Dijkstra {
+    fn name(&self) -> &'static str {
+        "Dijkstra's Shortest Path"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        check_no_negative_edges(graph, "Dijkstra's")?;
+
+        *self = Dijkstra {
+            start: Some(start),
+            end,
+            queue: BinaryHeap::from([Queued {
+                priority: 0.0,
+                node: start,
+            }]),
+            distances: HashMap::from([(start, 0.0)]),
+            visited: HashSet::new(),
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(Queued {
+            priority: distance,
+            node: idx,
+        }) = self.queue.pop()
+        else {
+            return;
+        };
+
+        mark_visited(graph, idx, self.start, self.end);
+        self.visited.insert(idx);
+
+        if Some(idx) != self.end {
+            for edge in graph.get_node(idx).get_edges() {
+                let (weight, _, b) = edge.get_weighted_nodes();
+
+                if self.visited.contains(&b) {
+                    continue;
+                }
+
+                let node_to_visit = graph.get_node_mut(b);
+                node_to_visit.view();
+
+                let new_distance = distance + weight;
+                if new_distance < self.distances.get(&b).copied().unwrap_or(f32::INFINITY) {
+                    self.distances.insert(b, new_distance);
+                    node_to_visit.set_last_node(idx, new_distance);
+
+                    self.queue.push(Queued {
+                        priority: new_distance,
+                        node: b,
+                    });
+                }
+            }
+        }
+
+        self.skip_visited();
+    }
+
+    fn finished(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        let mut queue = self.queue.clone().into_sorted_vec();
+        // The heap is ordered backwards so the sorted queue is too
+        queue.reverse();
+
+        let mut seen = HashSet::new();
+        queue
+            .into_iter()
+            .map(|q| q.node)
+            .filter(|n| !self.visited.contains(n) && seen.insert(*n))
+            .collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.iter().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{
+        flow::{flow_ui, FlowNetwork, MinCut},
+        Traverser,
+    },
+};
+
+/// Finds the maximum flow from the start to the end in phases
+///
+/// Each phase labels nodes with how far they are from the start, then sends flow along
+/// paths that only ever go one level further at a time until none are left, which is called
+/// a blocking flow. Each of those paths gets its own step.
+#[derive(Clone, Default)]
+pub struct Dinic {
+    network: FlowNetwork,
+    started: bool,
+    phases: usize,
+    /// How far each node is from the start this phase, none between phases
+    levels: Option<HashMap<NodeIndex, usize>>,
+    /// How many of each node's neighbors have been ruled out this phase
+    next_neighbor: HashMap<NodeIndex, usize>,
+    last_path: Option<(Vec<NodeIndex>, f32)>,
+    min_cut: Option<MinCut>,
+}
+
+impl Dinic {
+    /// Starts a new phase, labelling nodes with their levels, or shows the minimum cut if the
+    /// end can't be reached anymore
+    fn start_phase(&mut self, graph: &mut Graph) {
+        let levels = self.network.levels();
+
+        let nodes = graph
+            .get_nodes()
+            .iter()
+            .map(|n| n.get_id())
+            .collect::<Vec<_>>();
+        for idx in nodes {
+            graph
+                .get_node_mut(idx)
+                .set_badge(levels.get(&idx).map(|l| format!("L{l}")));
+        }
+
+        self.network.unmark(graph);
+        self.last_path = None;
+
+        if matches!(self.network.sink, Some(sink) if levels.contains_key(&sink)) {
+            self.phases += 1;
+            self.levels = Some(levels);
+            self.next_neighbor.clear();
+        } else {
+            self.min_cut = Some(self.network.show_min_cut(graph));
+        }
+    }
+
+    /// Searches for a path that only goes up a level at a time, skipping over anywhere that's
+    /// already led to a dead end this phase
+    fn find_path(&mut self) -> Option<Vec<NodeIndex>> {
+        let (source, sink) = (self.network.source?, self.network.sink?);
+        let levels = self.levels.as_ref()?;
+
+        let mut path = vec![source];
+
+        while let Some(idx) = path.last().copied() {
+            if idx == sink {
+                return Some(path);
+            }
+
+            let next = self.next_neighbor.entry(idx).or_default();
+            let Some(n) = self.network.neighbors(idx).get(*next).copied() else {
+                // Nothing gets through here so it's ruled out for whoever led us here
+                path.pop();
+                if let Some(prev) = path.last() {
+                    *self.next_neighbor.entry(*prev).or_default() += 1;
+                }
+                continue;
+            };
+
+            if levels.get(&n) == Some(&(levels[&idx] + 1)) && self.network.has_residual(idx, n) {
+                path.push(n);
+            } else {
+                *next += 1;
+            }
+        }
+
+        None
+    }
+}
+
+impl Traverser for Dinic {
+    fn name(&self) -> &'static str {
+        "Dinic's Max Flow"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        *self = Dinic {
+            network: FlowNetwork::new(graph, start, end, "Dinic's")?,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        if !self.started {
+            self.started = true;
+            self.network.show_empty_flows(graph);
+            return;
+        }
+
+        if self.levels.is_none() {
+            self.start_phase(graph);
+            return;
+        }
+
+        match self.find_path() {
+            Some(path) => {
+                let amount = self.network.augment(graph, &path);
+                self.last_path = Some((path, amount));
+            }
+            // The blocking flow is done so we go straight on to the next phase
+            None => {
+                self.levels = None;
+                self.start_phase(graph);
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.min_cut.is_some()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        Vec::new()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.last_path
+            .as_ref()
+            .map(|(path, _)| path.clone())
+            .unwrap_or_default()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        if self.phases > 0 && self.min_cut.is_none() {
+            ui.label(format!("Phase {}", self.phases));
+        }
+
+        flow_ui(
+            ui,
+            graph,
+            &self.network,
+            self.last_path.as_ref(),
+            self.min_cut.as_ref(),
+        );
+    }
+}
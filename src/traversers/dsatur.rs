@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{
+        coloring::{adjacency, color_node, coloring_ui, smallest_free_color},
+        Traverser,
+    },
+};
+
+/// Colors whichever node has neighbors with the most different colors next, since it has the
+/// fewest colors left to pick from
+///
+/// Ties go to the node with the most uncolored neighbors. Each uncolored node's badge shows
+/// how many different colors its neighbors have, which is called its saturation.
+#[derive(Clone, Default)]
+pub struct DSatur {
+    nodes: Vec<NodeIndex>,
+    adjacency: HashMap<NodeIndex, Vec<NodeIndex>>,
+    colors: HashMap<NodeIndex, usize>,
+    /// The order nodes were colored in
+    visited: Vec<NodeIndex>,
+}
+
+impl DSatur {
+    fn saturation(&self, idx: NodeIndex) -> usize {
+        self.adjacency[&idx]
+            .iter()
+            .filter_map(|n| self.colors.get(n))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    fn uncolored_degree(&self, idx: NodeIndex) -> usize {
+        self.adjacency[&idx]
+            .iter()
+            .filter(|n| !self.colors.contains_key(n))
+            .count()
+    }
+
+    /// The uncolored nodes, with the one to color next first
+    fn uncolored(&self) -> Vec<NodeIndex> {
+        let mut uncolored = self
+            .nodes
+            .iter()
+            .filter(|n| !self.colors.contains_key(n))
+            .copied()
+            .collect::<Vec<_>>();
+
+        uncolored
+            .sort_by_key(|n| std::cmp::Reverse((self.saturation(*n), self.uncolored_degree(*n))));
+        uncolored
+    }
+}
+
+impl Traverser for DSatur {
+    fn name(&self) -> &'static str {
+        "DSatur Coloring"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        *self = DSatur {
+            nodes: graph.get_nodes().iter().map(|n| n.get_id()).collect(),
+            adjacency: adjacency(graph),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(idx) = self.uncolored().first().copied() else {
+            return;
+        };
+
+        let color = smallest_free_color(&self.adjacency[&idx], &self.colors);
+        self.colors.insert(idx, color);
+        self.visited.push(idx);
+
+        color_node(graph, idx, color);
+        graph.get_node_mut(idx).set_badge(None);
+
+        for n in self.adjacency[&idx].clone() {
+            if !self.colors.contains_key(&n) {
+                let node = graph.get_node_mut(n);
+                node.view();
+                node.set_badge(Some(format!("sat {}", self.saturation(n))));
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.colors.len() == self.nodes.len()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.uncolored()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.clone()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        coloring_ui(ui, graph, &self.colors, self.finished());
+    }
+}
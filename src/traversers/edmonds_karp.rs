@@ -0,0 +1,126 @@
+use std::collections::{HashMap, VecDeque};
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{
+        flow::{flow_ui, FlowNetwork, MinCut},
+        Traverser,
+    },
+};
+
+/// Finds the maximum flow from the start to the end by sending as much as possible along the
+/// shortest path with room left, one path per step
+///
+/// Edge weights are the capacities, and once there's no path left the nodes the start can
+/// still reach are one side of the minimum cut.
+#[derive(Clone, Default)]
+pub struct EdmondsKarp {
+    network: FlowNetwork,
+    started: bool,
+    last_path: Option<(Vec<NodeIndex>, f32)>,
+    min_cut: Option<MinCut>,
+}
+
+impl EdmondsKarp {
+    /// Searches breadth first for the shortest path with room left
+    fn shortest_path(&self) -> Option<Vec<NodeIndex>> {
+        let (source, sink) = (self.network.source?, self.network.sink?);
+
+        let mut previous = HashMap::new();
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(idx) = queue.pop_front() {
+            if idx == sink {
+                let mut path = vec![sink];
+                while let Some(prev) = previous.get(path.last()?) {
+                    path.push(*prev);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for n in self.network.neighbors(idx) {
+                if *n != source && !previous.contains_key(n) && self.network.has_residual(idx, *n) {
+                    previous.insert(*n, idx);
+                    queue.push_back(*n);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl Traverser for EdmondsKarp {
+    fn name(&self) -> &'static str {
+        "Edmonds-Karp Max Flow"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        *self = EdmondsKarp {
+            network: FlowNetwork::new(graph, start, end, "Edmonds-Karp")?,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        if !self.started {
+            self.started = true;
+            self.network.show_empty_flows(graph);
+            return;
+        }
+
+        match self.shortest_path() {
+            Some(path) => {
+                let amount = self.network.augment(graph, &path);
+                self.last_path = Some((path, amount));
+            }
+            None => {
+                self.last_path = None;
+                self.min_cut = Some(self.network.show_min_cut(graph));
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.min_cut.is_some()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        Vec::new()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.last_path
+            .as_ref()
+            .map(|(path, _)| path.clone())
+            .unwrap_or_default()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        flow_ui(
+            ui,
+            graph,
+            &self.network,
+            self.last_path.as_ref(),
+            self.min_cut.as_ref(),
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+
+use egui::{ScrollArea, Ui};
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex},
+    traversers::{connected_nodes, Traverser},
+};
+
+/// Finds a path that goes along every edge exactly once using Hierholzer's algorithm
+///
+/// The first step checks every node's degree, since all but the two ends of the path need as
+/// many ways in as out. After that each step walks one edge of a sub-tour, going until it gets
+/// stuck. A finished sub-tour is spliced into the tour where it started, and the next one starts
+/// from the first node on the tour that still has edges left.
+#[derive(Clone, Default)]
+pub struct Eulerian {
+    directed: bool,
+    /// The edges not walked yet, stored on both ends if the graph is undirected
+    unused: HashMap<NodeIndex, Vec<NodeIndex>>,
+    started: bool,
+    /// Why there's no Eulerian path, one reason per line
+    problems: Vec<String>,
+    /// Where the path starts and ends, which are the same for a circuit
+    ends: Option<(NodeIndex, NodeIndex)>,
+    tour: Vec<NodeIndex>,
+    sub_tour: Vec<NodeIndex>,
+    /// Where in the tour the current sub-tour will be spliced in
+    splice_at: usize,
+    /// How many sub-tours have been spliced into the tour
+    splices: usize,
+    edge_count: usize,
+}
+
+impl Eulerian {
+    /// Checks every node has a degree that allows an Eulerian path, labelling them with their
+    /// degrees and putting rings around any that will have to be an end
+    fn check_degrees(&mut self, graph: &mut Graph) {
+        let name = |idx: NodeIndex, graph: &Graph| graph.get_node(idx).get_name().to_owned();
+
+        let nodes = graph
+            .get_nodes()
+            .iter()
+            .map(|n| n.get_id())
+            .collect::<Vec<_>>();
+
+        let mut in_degrees = HashMap::<_, usize>::new();
+        for edge in graph.get_edges() {
+            *in_degrees.entry(edge.get_nodes().1).or_default() += 1;
+        }
+
+        let mut starts = Vec::new();
+        let mut ends = Vec::new();
+        let mut odd = Vec::new();
+
+        for idx in nodes.iter().copied() {
+            let out_degree = self.unused[&idx].len();
+            let node = graph.get_node_mut(idx);
+
+            if self.directed {
+                let in_degree = in_degrees.get(&idx).copied().unwrap_or_default();
+                node.set_badge(Some(format!("{in_degree} in {out_degree} out")));
+
+                match out_degree as isize - in_degree as isize {
+                    0 => {}
+                    1 => starts.push(idx),
+                    -1 => ends.push(idx),
+                    difference => self.problems.push(format!(
+                        "{} has {} more edges going {} than {}",
+                        name(idx, graph),
+                        difference.abs(),
+                        if difference > 0 { "out" } else { "in" },
+                        if difference > 0 { "in" } else { "out" },
+                    )),
+                }
+            } else {
+                node.set_badge(Some(format!("deg {out_degree}")));
+
+                if out_degree % 2 == 1 {
+                    odd.push(idx);
+                }
+            }
+        }
+
+        for idx in starts.iter().chain(&ends).chain(&odd) {
+            graph.get_node_mut(*idx).set_ring(true);
+        }
+
+        let names = |nodes: &[NodeIndex], graph: &Graph| {
+            nodes
+                .iter()
+                .map(|n| name(*n, graph))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        if self.directed {
+            if starts.len() > 1 {
+                self.problems.push(format!(
+                    "{} all have one more edge going out than in, only the start can",
+                    names(&starts, graph)
+                ));
+            }
+
+            if ends.len() > 1 {
+                self.problems.push(format!(
+                    "{} all have one more edge coming in than out, only the end can",
+                    names(&ends, graph)
+                ));
+            }
+
+            if starts.len() != ends.len() && starts.len() < 2 && ends.len() < 2 {
+                self.problems.push(
+                    "A path with a different start and end needs one node with an extra edge \
+                     going out and one with an extra edge coming in"
+                        .to_owned(),
+                );
+            }
+        } else if odd.len() > 2 {
+            self.problems.push(format!(
+                "{} nodes have an odd number of edges ({}), only the two ends of a path can",
+                odd.len(),
+                names(&odd, graph)
+            ));
+        }
+
+        let with_edges = nodes
+            .iter()
+            .copied()
+            .filter(|n| !self.unused[n].is_empty() || in_degrees.contains_key(n))
+            .collect::<Vec<_>>();
+
+        let Some(first) = with_edges.first().copied() else {
+            self.problems
+                .push("There aren't any edges to go along".to_owned());
+            return;
+        };
+
+        let connected = connected_nodes(graph, first);
+        let cut_off = with_edges
+            .iter()
+            .copied()
+            .filter(|n| !connected.contains(n))
+            .collect::<Vec<_>>();
+        if !cut_off.is_empty() {
+            self.problems.push(format!(
+                "{} can't be reached from {}, so no one path can go along every edge",
+                names(&cut_off, graph),
+                name(first, graph)
+            ));
+        }
+
+        if !self.problems.is_empty() {
+            return;
+        }
+
+        let start = starts.first().or(odd.first()).copied().unwrap_or(first);
+        let end = ends.first().or(odd.get(1)).copied().unwrap_or(start);
+        self.ends = Some((start, end));
+
+        self.sub_tour = vec![start];
+        graph.get_node_mut(start).start();
+    }
+
+    /// Goes along an unused edge from `from` to `to`
+    fn take_edge(&mut self, from: NodeIndex, to: NodeIndex) {
+        if let Some(edges) = self.unused.get_mut(&from) {
+            if let Some(pos) = edges.iter().position(|n| *n == to) {
+                edges.remove(pos);
+            }
+        }
+
+        if !self.directed {
+            if let Some(edges) = self.unused.get_mut(&to) {
+                if let Some(pos) = edges.iter().position(|n| *n == from) {
+                    edges.remove(pos);
+                }
+            }
+        }
+    }
+
+    /// Puts the finished sub-tour into the tour and numbers the edges again
+    fn splice(&mut self, graph: &mut Graph) {
+        let sub_tour = std::mem::take(&mut self.sub_tour);
+
+        // Each time the sub-tour went along a pair of nodes it took a different edge between them
+        for pair in sub_tour.windows(2) {
+            graph.change_edge_state(pair[0], pair[1], EdgeState::Relaxed, EdgeState::Accepted);
+        }
+
+        if self.tour.is_empty() {
+            self.tour = sub_tour;
+        } else {
+            self.tour.splice(self.splice_at..=self.splice_at, sub_tour);
+        }
+        self.splices += 1;
+        graph.number_edges(&self.tour);
+
+        // The next sub-tour starts from the first node on the tour with edges left
+        if let Some(pos) = self.tour.iter().position(|n| !self.unused[n].is_empty()) {
+            self.splice_at = pos;
+            self.sub_tour = vec![self.tour[pos]];
+        }
+    }
+}
+
+impl Traverser for Eulerian {
+    fn name(&self) -> &'static str {
+        "Eulerian Path"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        *self = Eulerian {
+            directed: graph.is_directed(),
+            unused: graph
+                .get_nodes()
+                .iter()
+                .map(|n| {
+                    let edges = n.get_edges().iter().map(|e| e.get_nodes().1).collect();
+                    (n.get_id(), edges)
+                })
+                .collect(),
+            edge_count: graph.get_edges().len(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        if !self.started {
+            self.started = true;
+            self.check_degrees(graph);
+            return;
+        }
+
+        let Some(idx) = self.sub_tour.last().copied() else {
+            return;
+        };
+
+        match self.unused[&idx].first().copied() {
+            Some(next) => {
+                self.take_edge(idx, next);
+                self.sub_tour.push(next);
+
+                graph.change_edge_state(idx, next, EdgeState::None, EdgeState::Relaxed);
+                graph.get_node_mut(next).visit();
+            }
+            // A sub-tour can only get stuck back where it started, or at the end of the path
+            None => self.splice(graph),
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.started && (!self.problems.is_empty() || self.sub_tour.is_empty())
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.sub_tour.clone()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.tour.clone()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        let names = |nodes: &[NodeIndex]| {
+            nodes
+                .iter()
+                .map(|n| graph.get_node(*n).get_name())
+                .collect::<Vec<_>>()
+                .join(" → ")
+        };
+
+        if !self.problems.is_empty() {
+            ui.colored_label(ui.visuals().error_fg_color, "There's no Eulerian path");
+
+            for problem in &self.problems {
+                ui.colored_label(ui.visuals().error_fg_color, problem);
+            }
+            return;
+        }
+
+        if let Some((start, end)) = self.ends {
+            if start == end {
+                ui.label(format!(
+                    "There's an Eulerian circuit starting and ending at {}",
+                    graph.get_node(start).get_name()
+                ));
+            } else {
+                ui.label(format!(
+                    "There's an Eulerian path from {} to {}",
+                    graph.get_node(start).get_name(),
+                    graph.get_node(end).get_name()
+                ));
+            }
+        }
+
+        ui.label(format!(
+            "The tour has {} of {} edges after {} sub-tours",
+            self.tour.len().saturating_sub(1),
+            self.edge_count,
+            self.splices
+        ));
+
+        if self.sub_tour.len() > 1 {
+            ui.label(format!("Current sub-tour: {}", names(&self.sub_tour)));
+        }
+
+        if !self.tour.is_empty() {
+            ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                ui.monospace(names(&self.tour));
+            });
+        }
+    }
+}
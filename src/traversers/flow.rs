@@ -0,0 +1,272 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use egui::{ScrollArea, Ui};
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex},
+    traversers::check_no_negative_edges,
+};
+
+/// Anything left over smaller than this is just rounding
+const EPSILON: f32 = 1e-6;
+
+/// A graph's edges treated as pipes, keeping track of how much is flowing through them
+#[derive(Clone, Default)]
+pub struct FlowNetwork {
+    pub source: Option<NodeIndex>,
+    pub sink: Option<NodeIndex>,
+    capacities: HashMap<(NodeIndex, NodeIndex), f32>,
+    flows: HashMap<(NodeIndex, NodeIndex), f32>,
+    /// Every node each node shares an edge with in either direction, since flow can be
+    /// pushed back along an edge as well
+    neighbors: HashMap<NodeIndex, Vec<NodeIndex>>,
+    /// The edges marked for the last augmenting path
+    marked: Vec<(NodeIndex, NodeIndex)>,
+}
+
+impl FlowNetwork {
+    /// Builds the network using edge weights as capacities, or explains why we can't.
+    /// An undirected edge becomes a pipe each way, so flow can go along it in either direction.
+    pub fn new(
+        graph: &Graph,
+        source: Option<NodeIndex>,
+        sink: Option<NodeIndex>,
+        name: &str,
+    ) -> Result<Self, String> {
+        check_no_negative_edges(graph, name)?;
+
+        let (Some(source), Some(sink)) = (source, sink) else {
+            return Err(format!("{name} needs a source and sink to flow between"));
+        };
+
+        if source == sink {
+            return Err("The source and sink need to be different nodes".to_owned());
+        }
+
+        let mut network = FlowNetwork {
+            source: Some(source),
+            sink: Some(sink),
+            ..Default::default()
+        };
+
+        for edge in graph.get_edges() {
+            let (capacity, a, b) = edge.get_weighted_nodes();
+            *network.capacities.entry((a, b)).or_default() += capacity;
+            if !graph.is_directed() {
+                *network.capacities.entry((b, a)).or_default() += capacity;
+            }
+
+            for (from, to) in [(a, b), (b, a)] {
+                let neighbors = network.neighbors.entry(from).or_default();
+                if !neighbors.contains(&to) {
+                    neighbors.push(to);
+                }
+            }
+        }
+
+        Ok(network)
+    }
+
+    fn capacity(&self, a: NodeIndex, b: NodeIndex) -> f32 {
+        self.capacities.get(&(a, b)).copied().unwrap_or(0.0)
+    }
+
+    fn flow(&self, a: NodeIndex, b: NodeIndex) -> f32 {
+        self.flows.get(&(a, b)).copied().unwrap_or(0.0)
+    }
+
+    /// How much more could be sent from `a` to `b`, including sending back what's flowing
+    /// from `b` to `a`
+    pub fn residual(&self, a: NodeIndex, b: NodeIndex) -> f32 {
+        self.capacity(a, b) - self.flow(a, b) + self.flow(b, a)
+    }
+
+    pub fn has_residual(&self, a: NodeIndex, b: NodeIndex) -> bool {
+        self.residual(a, b) > EPSILON
+    }
+
+    pub fn neighbors(&self, idx: NodeIndex) -> &[NodeIndex] {
+        self.neighbors.get(&idx).map_or(&[], |n| n.as_slice())
+    }
+
+    /// How much is flowing out of the source
+    pub fn total_flow(&self) -> f32 {
+        let Some(source) = self.source else {
+            return 0.0;
+        };
+
+        self.neighbors(source)
+            .iter()
+            .map(|n| self.flow(source, *n) - self.flow(*n, source))
+            .sum()
+    }
+
+    /// Shows every edge with nothing flowing through it yet
+    pub fn show_empty_flows(&self, graph: &mut Graph) {
+        for (a, b) in self.capacities.keys() {
+            graph.set_edge_flow(*a, *b, Some(0.0));
+        }
+
+        if let Some(source) = self.source {
+            graph.get_node_mut(source).start();
+        }
+        if let Some(sink) = self.sink {
+            graph.get_node_mut(sink).end();
+        }
+    }
+
+    /// Clears the marks from the last augmenting path
+    pub fn unmark(&mut self, graph: &mut Graph) {
+        for (a, b) in self.marked.drain(..) {
+            graph.set_edge_state(a, b, EdgeState::None);
+        }
+    }
+
+    /// Sends as much as we can along a path, returning how much that was
+    pub fn augment(&mut self, graph: &mut Graph, path: &[NodeIndex]) -> f32 {
+        self.unmark(graph);
+
+        let amount = path
+            .windows(2)
+            .map(|w| self.residual(w[0], w[1]))
+            .fold(f32::INFINITY, f32::min);
+
+        for w in path.windows(2) {
+            let (a, b) = (w[0], w[1]);
+
+            // Cancelling out flow going the other way comes first
+            let back = amount.min(self.flow(b, a));
+            *self.flows.entry((b, a)).or_default() -= back;
+            *self.flows.entry((a, b)).or_default() += amount - back;
+
+            for (from, to) in [(a, b), (b, a)] {
+                if self.capacities.contains_key(&(from, to)) {
+                    graph.set_edge_flow(from, to, Some(self.flow(from, to)));
+                }
+            }
+
+            let edge = if self.capacities.contains_key(&(a, b)) {
+                (a, b)
+            } else {
+                (b, a)
+            };
+            graph.set_edge_state(edge.0, edge.1, EdgeState::Relaxed);
+            self.marked.push(edge);
+
+            if Some(b) != self.sink {
+                graph.get_node_mut(b).visit();
+            }
+        }
+
+        amount
+    }
+
+    /// Finds how far each node is from the source, only going along edges with room left
+    pub fn levels(&self) -> HashMap<NodeIndex, usize> {
+        let mut levels = HashMap::new();
+        let Some(source) = self.source else {
+            return levels;
+        };
+
+        levels.insert(source, 0);
+        let mut queue = VecDeque::from([source]);
+
+        while let Some(idx) = queue.pop_front() {
+            for n in self.neighbors(idx) {
+                if !levels.contains_key(n) && self.has_residual(idx, *n) {
+                    levels.insert(*n, levels[&idx] + 1);
+                    queue.push_back(*n);
+                }
+            }
+        }
+
+        levels
+    }
+
+    /// Colors the two sides of the minimum cut, which is everything the source can still
+    /// reach and everything it can't
+    pub fn show_min_cut(&mut self, graph: &mut Graph) -> MinCut {
+        self.unmark(graph);
+
+        let source_side = self.levels().into_keys().collect::<HashSet<_>>();
+
+        let nodes = graph
+            .get_nodes()
+            .iter()
+            .map(|n| n.get_id())
+            .collect::<Vec<_>>();
+        for idx in &nodes {
+            let side = usize::from(!source_side.contains(idx));
+            graph.get_node_mut(*idx).set_group(Some(side));
+        }
+
+        let mut cut_edges = self
+            .capacities
+            .keys()
+            .filter(|(a, b)| source_side.contains(a) && !source_side.contains(b))
+            .copied()
+            .collect::<Vec<_>>();
+        cut_edges.sort_by_key(|(a, b)| {
+            (
+                nodes.iter().position(|n| n == a),
+                nodes.iter().position(|n| n == b),
+            )
+        });
+
+        MinCut {
+            source_side: source_side.len(),
+            sink_side: nodes.len() - source_side.len(),
+            edges: cut_edges,
+        }
+    }
+}
+
+/// The edges that would have to be cut to stop anything getting from the source to the sink
+#[derive(Clone)]
+pub struct MinCut {
+    source_side: usize,
+    sink_side: usize,
+    edges: Vec<(NodeIndex, NodeIndex)>,
+}
+
+/// Shows how much is flowing, the last path something was sent along, and the cut once
+/// we're done
+pub fn flow_ui(
+    ui: &mut Ui,
+    graph: &Graph,
+    network: &FlowNetwork,
+    last_path: Option<&(Vec<NodeIndex>, f32)>,
+    min_cut: Option<&MinCut>,
+) {
+    let name = |idx: &NodeIndex| graph.get_node(*idx).get_name();
+
+    match min_cut {
+        Some(_) => ui.label(format!("Maximum flow: {}", network.total_flow())),
+        None => ui.label(format!("Flow so far: {}", network.total_flow())),
+    };
+
+    if let Some((path, amount)) = last_path {
+        ui.label(format!(
+            "Sent {amount} along {}",
+            path.iter().map(name).collect::<Vec<_>>().join(" → ")
+        ));
+    }
+
+    if let Some(cut) = min_cut {
+        ui.label(format!(
+            "The minimum cut splits {} nodes on the source side from {} on the sink side, through",
+            cut.source_side, cut.sink_side
+        ));
+
+        ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+            for (a, b) in &cut.edges {
+                ui.monospace(format!(
+                    "{} → {} ({})",
+                    name(a),
+                    name(b),
+                    network.capacity(*a, *b)
+                ));
+            }
+        });
+    }
+}
@@ -0,0 +1,210 @@
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{
+        all_pairs::{check_matrix_size, eccentricity_ui, DistanceMatrix},
+        cycle_names, mark_cycle, Traverser,
+    },
+};
+
+/// Each node takes a step for every pair of nodes, so any more nodes than this and there would
+/// be far too many steps to keep track of, well before the matrix gets too big to show
+const MAX_STEPPED_NODES: usize = 30;
+
+/// Finds the shortest paths between every pair of nodes
+///
+/// Each round lets paths go through one more node, checking every pair of nodes to see if
+/// going through it is shorter. Each step is one cell of the distance matrix getting shorter,
+/// or a round finishing.
+#[derive(Clone, Default)]
+pub struct FloydWarshall {
+    matrix: DistanceMatrix,
+    /// The node paths are allowed to go through this round
+    k: usize,
+    /// The next cell to check this round
+    cell: (usize, usize),
+    negative_cycle: Option<Vec<NodeIndex>>,
+}
+
+impl FloydWarshall {
+    /// Goes back from `i` along the path it took to get back to itself
+    fn find_cycle(&self, i: usize) -> Vec<NodeIndex> {
+        let mut cycle = vec![i];
+        let mut curr = self.matrix.get_previous(i, i);
+
+        while let Some(prev) = curr.filter(|p| *p != i && cycle.len() < self.matrix.nodes.len()) {
+            cycle.push(prev);
+            curr = self.matrix.get_previous(i, prev);
+        }
+
+        cycle.reverse();
+        cycle.into_iter().map(|p| self.matrix.nodes[p]).collect()
+    }
+
+    /// Moves on to the next cell, going off the bottom of the matrix once the round is over
+    fn next_cell(&mut self) {
+        let (i, j) = &mut self.cell;
+
+        *j += 1;
+        if *j == self.matrix.nodes.len() {
+            *j = 0;
+            *i += 1;
+        }
+    }
+}
+
+impl Traverser for FloydWarshall {
+    fn name(&self) -> &'static str {
+        "Floyd-Warshall"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_matrix_size(graph, "Floyd-Warshall")?;
+        if graph.get_nodes().len() > MAX_STEPPED_NODES {
+            return Err(format!(
+                "Floyd-Warshall steps through every pair of nodes for each node so it's limited \
+                 to {MAX_STEPPED_NODES} nodes, try Johnson's instead"
+            ));
+        }
+
+        let mut matrix = DistanceMatrix::new(graph);
+
+        for edge in graph.get_nodes().iter().flat_map(|n| n.get_edges()) {
+            let (weight, a, b) = edge.get_weighted_nodes();
+            let (Some(i), Some(j)) = (matrix.position(a), matrix.position(b)) else {
+                continue;
+            };
+
+            if weight < matrix.get(i, j) {
+                matrix.set(i, j, weight, i);
+            }
+        }
+        matrix.updated.clear();
+
+        *self = FloydWarshall {
+            matrix,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        self.matrix.updated.clear();
+
+        let Some(through) = self.matrix.nodes.get(self.k).copied() else {
+            return;
+        };
+        graph.get_node_mut(through).set_ring(true);
+
+        let k = self.k;
+        while self.cell.0 < self.matrix.nodes.len() {
+            let (i, j) = self.cell;
+            self.next_cell();
+
+            let distance = self.matrix.get(i, k) + self.matrix.get(k, j);
+
+            if distance < self.matrix.get(i, j) {
+                let previous = self.matrix.get_previous(k, j).unwrap_or(k);
+                self.matrix.set(i, j, distance, previous);
+
+                if i == j {
+                    let cycle = self.find_cycle(i);
+                    mark_cycle(graph, &cycle);
+                    self.negative_cycle = Some(cycle);
+                    graph.get_node_mut(through).set_ring(false);
+                    return;
+                }
+
+                for idx in [self.matrix.nodes[i], self.matrix.nodes[j]] {
+                    graph.get_node_mut(idx).view();
+                }
+                return;
+            }
+        }
+
+        // Every pair has had the chance to go through this node
+        let node = graph.get_node_mut(through);
+        node.set_ring(false);
+        node.visit();
+
+        self.k += 1;
+        self.cell = (0, 0);
+    }
+
+    fn finished(&self) -> bool {
+        self.negative_cycle.is_some() || self.k >= self.matrix.nodes.len()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.matrix.nodes.get(self.k..).unwrap_or_default().to_vec()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.matrix.nodes[..self.k.min(self.matrix.nodes.len())].to_vec()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        let name = |i: usize| graph.get_node(self.matrix.nodes[i]).get_name();
+
+        if let Some(cycle) = &self.negative_cycle {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!(
+                    "Found a negative cycle, so some paths can keep getting shorter forever: {}",
+                    cycle_names(graph, cycle)
+                ),
+            );
+            return;
+        }
+
+        if self.finished() {
+            ui.label("Found the shortest path between every pair of nodes");
+            eccentricity_ui(ui, graph, &self.matrix);
+            return;
+        }
+
+        ui.label(format!(
+            "Round {} of {}, going through {}",
+            self.k + 1,
+            self.matrix.nodes.len(),
+            name(self.k)
+        ));
+
+        if let Some((i, j)) = self.matrix.updated.first() {
+            ui.label(format!(
+                "Going from {} to {} through {} is shorter, {}",
+                name(*i),
+                name(*j),
+                name(self.k),
+                self.matrix.get(*i, *j)
+            ));
+        }
+    }
+
+    fn distance_matrix(&self) -> Option<&DistanceMatrix> {
+        Some(&self.matrix)
+    }
+}
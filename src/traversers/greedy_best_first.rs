@@ -0,0 +1,149 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use egui::{Pos2, Ui};
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{
+        astar::{heuristic_ui, Heuristic},
+        mark_visited, require_start, Queued, Traverser,
+    },
+};
+
+/// Always goes to whichever node it's found that looks closest to the end, without caring how
+/// far it's come
+///
+/// It's A* with the distance so far left out, so it usually visits fewer nodes but the path it
+/// finds can be a lot longer than the shortest one. Each node's badge shows its h.
+#[derive(Clone, Default)]
+pub struct GreedyBestFirst {
+    heuristic: Heuristic,
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+    end_pos: Pos2,
+    queue: BinaryHeap<Queued>,
+    /// The length of the path each node was found along
+    distances: HashMap<NodeIndex, f32>,
+    visited: HashSet<NodeIndex>,
+    found: bool,
+}
+
+impl GreedyBestFirst {
+    fn remaining(&self, graph: &Graph, idx: NodeIndex) -> f32 {
+        self.heuristic
+            .estimate(graph.get_node(idx).get_pos().into(), self.end_pos)
+    }
+}
+
+impl Traverser for GreedyBestFirst {
+    fn name(&self) -> &'static str {
+        "Greedy Best-First Search"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        let Some(end) = end else {
+            return Err("Greedy best-first search needs an end node to head towards".to_owned());
+        };
+
+        *self = GreedyBestFirst {
+            heuristic: self.heuristic,
+            start: Some(start),
+            end: Some(end),
+            end_pos: graph.get_node(end).get_pos().into(),
+            distances: HashMap::from([(start, 0.0)]),
+            ..Default::default()
+        };
+        self.queue.push(Queued {
+            priority: self.remaining(graph, start),
+            node: start,
+        });
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(Queued {
+            priority: remaining,
+            node: idx,
+        }) = self.queue.pop()
+        else {
+            return;
+        };
+
+        mark_visited(graph, idx, self.start, self.end);
+        graph
+            .get_node_mut(idx)
+            .set_badge(Some(format!("h {remaining:.1}")));
+        self.visited.insert(idx);
+
+        if Some(idx) == self.end {
+            self.found = true;
+            return;
+        }
+
+        let distance = self.distances[&idx];
+
+        for edge in graph.get_node(idx).get_edges() {
+            let (weight, _, b) = edge.get_weighted_nodes();
+
+            // Nodes are only queued once, the first time they're found
+            if self.distances.contains_key(&b) {
+                continue;
+            }
+
+            let remaining = self.remaining(graph, b);
+
+            let node_to_visit = graph.get_node_mut(b);
+            node_to_visit.view();
+            node_to_visit.set_last_node(idx, distance + weight);
+            node_to_visit.set_badge(Some(format!("h {remaining:.1}")));
+
+            self.distances.insert(b, distance + weight);
+            self.queue.push(Queued {
+                priority: remaining,
+                node: b,
+            });
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.found || self.queue.is_empty()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        let mut queue = self.queue.clone().into_sorted_vec();
+        // The heap is ordered backwards so the sorted queue is too
+        queue.reverse();
+        queue.into_iter().map(|q| q.node).collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.iter().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn settings_ui(&mut self, ui: &mut Ui) {
+        heuristic_ui(ui, &mut self.heuristic);
+    }
+
+    fn results_ui(&self, _graph: &Graph, ui: &mut Ui) {
+        if self.found {
+            if let Some(distance) = self.end.and_then(|end| self.distances.get(&end)) {
+                ui.label(format!(
+                    "Found a path of length {distance}, which might not be the shortest"
+                ));
+            }
+        } else if self.finished() {
+            ui.label("The end can't be reached from the start");
+        }
+    }
+}
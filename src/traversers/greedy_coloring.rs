@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use egui::{ComboBox, Ui};
+use rand::seq::SliceRandom;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{
+        coloring::{adjacency, color_node, coloring_ui, largest_first, smallest_free_color},
+        Traverser,
+    },
+};
+
+/// The order greedy coloring goes through the nodes in
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColoringOrder {
+    #[default]
+    Graph,
+    Random,
+    LargestFirst,
+    SmallestLast,
+}
+
+impl ColoringOrder {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            ColoringOrder::Graph => "Graph Order",
+            ColoringOrder::Random => "Random Order",
+            ColoringOrder::LargestFirst => "Most Neighbors First",
+            ColoringOrder::SmallestLast => "Fewest Neighbors Last",
+        }
+    }
+
+    pub const fn values() -> [ColoringOrder; 4] {
+        [
+            ColoringOrder::Graph,
+            ColoringOrder::Random,
+            ColoringOrder::LargestFirst,
+            ColoringOrder::SmallestLast,
+        ]
+    }
+}
+
+/// Gives each node the lowest color none of its neighbors have, going through the nodes in
+/// whichever order was picked
+///
+/// This never needs more colors than the most neighbors any node has plus one, but how close
+/// it gets to the fewest possible depends a lot on the order.
+#[derive(Clone, Default)]
+pub struct GreedyColoring {
+    order_kind: ColoringOrder,
+    order: Vec<NodeIndex>,
+    next: usize,
+    adjacency: HashMap<NodeIndex, Vec<NodeIndex>>,
+    colors: HashMap<NodeIndex, usize>,
+}
+
+/// Takes out the node with the fewest neighbors left over and over, then colors them in the
+/// reverse order they were taken out
+fn smallest_last(graph: &Graph, adjacency: &HashMap<NodeIndex, Vec<NodeIndex>>) -> Vec<NodeIndex> {
+    let mut degrees = graph
+        .get_nodes()
+        .iter()
+        .map(|n| (n.get_id(), adjacency[&n.get_id()].len()))
+        .collect::<Vec<_>>();
+    let mut order = Vec::new();
+
+    while let Some(pos) = (0..degrees.len()).min_by_key(|i| degrees[*i].1) {
+        let (idx, _) = degrees.remove(pos);
+
+        for (n, degree) in &mut degrees {
+            if adjacency[&idx].contains(n) {
+                *degree -= 1;
+            }
+        }
+
+        order.push(idx);
+    }
+
+    order.reverse();
+    order
+}
+
+impl Traverser for GreedyColoring {
+    fn name(&self) -> &'static str {
+        "Greedy Coloring"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let adjacency = adjacency(graph);
+
+        let order = match self.order_kind {
+            ColoringOrder::Graph => graph.get_nodes().iter().map(|n| n.get_id()).collect(),
+            ColoringOrder::Random => {
+                let mut order = graph
+                    .get_nodes()
+                    .iter()
+                    .map(|n| n.get_id())
+                    .collect::<Vec<_>>();
+                order.shuffle(&mut rand::thread_rng());
+                order
+            }
+            ColoringOrder::LargestFirst => largest_first(graph, &adjacency),
+            ColoringOrder::SmallestLast => smallest_last(graph, &adjacency),
+        };
+
+        *self = GreedyColoring {
+            order_kind: self.order_kind,
+            order,
+            adjacency,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some(idx) = self.order.get(self.next).copied() else {
+            return;
+        };
+        self.next += 1;
+
+        let color = smallest_free_color(&self.adjacency[&idx], &self.colors);
+        self.colors.insert(idx, color);
+        color_node(graph, idx, color);
+    }
+
+    fn finished(&self) -> bool {
+        self.next >= self.order.len()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.order[self.next..].to_vec()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.order[..self.next].to_vec()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn settings_ui(&mut self, ui: &mut Ui) {
+        let orders = ColoringOrder::values();
+        let mut curr_order = orders.iter().position(|o| *o == self.order_kind).unwrap();
+
+        ComboBox::from_label("Coloring Order").show_index(ui, &mut curr_order, orders.len(), |i| {
+            orders[i].name().to_owned()
+        });
+
+        self.order_kind = orders[curr_order];
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        coloring_ui(ui, graph, &self.colors, self.finished());
+    }
+}
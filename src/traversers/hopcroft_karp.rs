@@ -0,0 +1,261 @@
+use std::collections::{HashMap, VecDeque};
+
+use egui::{ScrollArea, Ui};
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex},
+    traversers::{bipartite::two_coloring, check_undirected, Traverser},
+};
+
+/// Finds the biggest set of edges that don't share any nodes in a bipartite graph
+///
+/// Each step is a phase, which finds the shortest paths that alternate between unmatched and
+/// matched edges and start and end on unmatched nodes. Flipping which edges along them are
+/// matched adds one more edge to the matching for each path.
+#[derive(Clone, Default)]
+pub struct HopcroftKarp {
+    sides: HashMap<NodeIndex, usize>,
+    /// The nodes on the first side, which paths start from
+    left: Vec<NodeIndex>,
+    neighbors: HashMap<NodeIndex, Vec<NodeIndex>>,
+    /// Who each matched node is matched with, kept for both ends
+    mates: HashMap<NodeIndex, NodeIndex>,
+    started: bool,
+    finished: bool,
+    phases: usize,
+    /// The paths flipped in the last phase
+    last_paths: Vec<Vec<NodeIndex>>,
+    /// Edges that were shown as part of a path last phase but aren't matched
+    marked: Vec<(NodeIndex, NodeIndex)>,
+}
+
+impl HopcroftKarp {
+    /// Labels each left node with how many edges it is along an alternating path from an
+    /// unmatched left node, stopping at the length of the shortest path to an unmatched
+    /// right node, or returns none if there isn't one
+    fn layers(&self) -> Option<(HashMap<NodeIndex, usize>, usize)> {
+        let mut layers = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        for idx in &self.left {
+            if !self.mates.contains_key(idx) {
+                layers.insert(*idx, 0);
+                queue.push_back(*idx);
+            }
+        }
+
+        let mut shortest = None;
+
+        while let Some(idx) = queue.pop_front() {
+            let layer = layers[&idx];
+            if matches!(shortest, Some(s) if layer >= s) {
+                continue;
+            }
+
+            for n in &self.neighbors[&idx] {
+                match self.mates.get(n) {
+                    None => shortest = Some(shortest.unwrap_or(layer + 1)),
+                    Some(mate) if !layers.contains_key(mate) => {
+                        layers.insert(*mate, layer + 1);
+                        queue.push_back(*mate);
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        shortest.map(|s| (layers, s))
+    }
+
+    /// Looks for a path from `idx` that goes up a layer each time it comes back to the left
+    /// side, ruling out nodes that lead nowhere so the paths found don't share any nodes
+    fn find_path(
+        &self,
+        idx: NodeIndex,
+        layers: &mut HashMap<NodeIndex, usize>,
+        shortest: usize,
+    ) -> Option<Vec<NodeIndex>> {
+        let layer = layers.remove(&idx)?;
+
+        for n in &self.neighbors[&idx] {
+            match self.mates.get(n) {
+                None if layer + 1 == shortest => return Some(vec![idx, *n]),
+                Some(mate) if layers.get(mate) == Some(&(layer + 1)) => {
+                    if let Some(rest) = self.find_path(*mate, layers, shortest) {
+                        let mut path = vec![idx, *n];
+                        path.extend(rest);
+                        return Some(path);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+
+    fn unmark(&mut self, graph: &mut Graph) {
+        for (a, b) in self.marked.drain(..) {
+            graph.set_edge_state(a, b, EdgeState::None);
+        }
+    }
+
+    /// Flips which edges along a path are matched
+    fn flip(&mut self, graph: &mut Graph, path: &[NodeIndex]) {
+        for (i, pair) in path.windows(2).enumerate() {
+            let (a, b) = (pair[0], pair[1]);
+
+            // Every other edge starting from the first one joins the matching
+            if i % 2 == 0 {
+                self.mates.insert(a, b);
+                self.mates.insert(b, a);
+                graph.set_edge_state(a, b, EdgeState::Matched);
+                graph.get_node_mut(a).visit();
+                graph.get_node_mut(b).visit();
+            } else {
+                graph.set_edge_state(a, b, EdgeState::Relaxed);
+                self.marked.push((a, b));
+            }
+        }
+    }
+
+    fn matching_size(&self) -> usize {
+        self.mates.len() / 2
+    }
+}
+
+impl Traverser for HopcroftKarp {
+    fn name(&self) -> &'static str {
+        "Hopcroft-Karp Matching"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_undirected(graph, "Hopcroft-Karp")?;
+
+        let Some(sides) = two_coloring(graph) else {
+            return Err(
+                "Hopcroft-Karp only works on bipartite graphs, the Bipartite Check can show why \
+                 this one isn't"
+                    .to_owned(),
+            );
+        };
+
+        *self = HopcroftKarp {
+            left: graph
+                .get_nodes()
+                .iter()
+                .map(|n| n.get_id())
+                .filter(|n| sides[n] == 0)
+                .collect(),
+            neighbors: graph
+                .get_nodes()
+                .iter()
+                .map(|n| (n.get_id(), n.get_neighbors()))
+                .collect(),
+            sides,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        if !self.started {
+            self.started = true;
+
+            for (idx, side) in &self.sides {
+                graph.get_node_mut(*idx).set_group(Some(*side));
+            }
+            return;
+        }
+
+        self.unmark(graph);
+        self.last_paths.clear();
+
+        let Some((mut layers, shortest)) = self.layers() else {
+            self.finished = true;
+            return;
+        };
+
+        self.phases += 1;
+
+        for idx in self.left.clone() {
+            if self.mates.contains_key(&idx) {
+                continue;
+            }
+
+            if let Some(path) = self.find_path(idx, &mut layers, shortest) {
+                self.flip(graph, &path);
+                self.last_paths.push(path);
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.finished
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        Vec::new()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.mates.keys().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        let name = |idx: &NodeIndex| graph.get_node(*idx).get_name();
+
+        if self.finished {
+            ui.label(format!(
+                "Maximum matching has {} edges after {} phases",
+                self.matching_size(),
+                self.phases
+            ));
+        } else {
+            ui.label(format!(
+                "Phase {}, matched {} edges so far",
+                self.phases,
+                self.matching_size()
+            ));
+        }
+
+        ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+            if !self.last_paths.is_empty() {
+                ui.label("Paths flipped this phase");
+                for path in &self.last_paths {
+                    ui.monospace(path.iter().map(name).collect::<Vec<_>>().join(" - "));
+                }
+            }
+
+            if self.finished {
+                for idx in &self.left {
+                    if let Some(mate) = self.mates.get(idx) {
+                        ui.monospace(format!("{} - {}", name(idx), name(mate)));
+                    }
+                }
+            }
+        });
+    }
+}
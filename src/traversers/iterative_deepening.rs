@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{mark_visited, require_start, Traverser},
+};
+
+/// Runs depth first searches that can't go past a depth limit, starting over with the limit one
+/// higher each time one runs out of nodes without finding the end
+///
+/// This finds the end by the fewest edges like breadth first search while only keeping the
+/// current path in memory like depth first search, at the cost of expanding nodes again every
+/// restart. Each node's badge shows how deep it was found this time around.
+#[derive(Clone, Default)]
+pub struct IterativeDeepening {
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+    depth_limit: usize,
+    restarts: usize,
+    /// The next node to expand is at the end, along with how deep it is
+    stack: Vec<(NodeIndex, usize)>,
+    /// The shallowest each node has been found this time around
+    depths: HashMap<NodeIndex, usize>,
+    /// Whether this search left out any nodes for being too deep
+    cut_off: bool,
+    visited: Vec<NodeIndex>,
+    expanded: usize,
+    found: bool,
+}
+
+impl IterativeDeepening {
+    fn restart(&mut self, graph: &mut Graph) {
+        graph.reset();
+
+        if let Some(start) = self.start {
+            self.stack = vec![(start, 0)];
+            self.depths = HashMap::from([(start, 0)]);
+        }
+        self.visited.clear();
+        self.cut_off = false;
+    }
+}
+
+impl Traverser for IterativeDeepening {
+    fn name(&self) -> &'static str {
+        "Iterative Deepening DFS"
+    }
+
+    fn init(
+        &mut self,
+        _graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        *self = IterativeDeepening {
+            start: Some(start),
+            end,
+            stack: vec![(start, 0)],
+            depths: HashMap::from([(start, 0)]),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        // Anything found again at a shallower depth since it was pushed is skipped
+        let next = std::iter::from_fn(|| self.stack.pop())
+            .find(|(idx, depth)| self.depths.get(idx) == Some(depth));
+
+        let Some((idx, depth)) = next else {
+            if self.cut_off {
+                self.depth_limit += 1;
+                self.restarts += 1;
+                self.restart(graph);
+            }
+            return;
+        };
+
+        mark_visited(graph, idx, self.start, self.end);
+        graph.get_node_mut(idx).set_badge(Some(format!("d{depth}")));
+        self.visited.push(idx);
+        self.expanded += 1;
+
+        if Some(idx) == self.end {
+            self.found = true;
+            return;
+        }
+
+        let neighbors = graph
+            .get_node(idx)
+            .get_neighbors()
+            .into_iter()
+            .filter(|n| !matches!(self.depths.get(n), Some(d) if *d <= depth + 1))
+            .collect::<Vec<_>>();
+
+        if depth == self.depth_limit {
+            self.cut_off |= !neighbors.is_empty();
+            return;
+        }
+
+        // Pushed backwards so the first neighbor gets expanded first
+        for n in neighbors.into_iter().rev() {
+            let node_to_visit = graph.get_node_mut(n);
+            node_to_visit.view();
+            node_to_visit.set_last_node(idx, (depth + 1) as f32);
+
+            self.depths.insert(n, depth + 1);
+            self.stack.push((n, depth + 1));
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.found || (self.stack.is_empty() && !self.cut_off)
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.stack.iter().rev().map(|(n, _)| *n).collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.visited.clone()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn nodes_expanded(&self) -> usize {
+        self.expanded
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        ui.label(format!(
+            "Depth limit {}, restarted {} times",
+            self.depth_limit, self.restarts
+        ));
+
+        if self.found {
+            if let Some(end) = self.end {
+                ui.label(format!(
+                    "Found {} at depth {}",
+                    graph.get_node(end).get_name(),
+                    self.depths[&end]
+                ));
+            }
+        } else if self.finished() {
+            ui.label("Every node that can be reached has been expanded");
+        } else if self.stack.is_empty() {
+            ui.label("Nothing left under the depth limit, starting again one deeper");
+        }
+    }
+}
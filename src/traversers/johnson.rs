@@ -0,0 +1,283 @@
+use std::collections::{BinaryHeap, HashMap};
+
+use egui::Ui;
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex},
+    traversers::{
+        all_pairs::{check_matrix_size, eccentricity_ui, DistanceMatrix},
+        cycle_names, mark_cycle, Queued, Traverser,
+    },
+};
+
+/// Finds the shortest paths between every pair of nodes by running Dijkstra's from each one,
+/// which is quicker than Floyd-Warshall when there aren't many edges
+///
+/// Negative weights would throw Dijkstra's off, so first Bellman-Ford works out a potential
+/// for each node, shown in its badge, one pass per step. Adding the difference in potential
+/// to every edge makes them all non-negative without changing which paths are shortest. After
+/// that each step runs Dijkstra's from one node and fills in its row of the distance matrix.
+#[derive(Clone, Default)]
+pub struct Johnson {
+    matrix: DistanceMatrix,
+    positions: HashMap<NodeIndex, usize>,
+    /// Each node's outgoing edges as the node they go to and their weight
+    edges: Vec<Vec<(usize, f32)>>,
+    potentials: Vec<f32>,
+    /// Which node each potential came from, none if it came straight from the extra node
+    /// Bellman-Ford starts from
+    predecessors: Vec<Option<usize>>,
+    passes: usize,
+    /// Whether Bellman-Ford has finished working out the potentials
+    reweighted: bool,
+    /// How many nodes Dijkstra's has been run from
+    sources: usize,
+    relaxed: Vec<(NodeIndex, NodeIndex)>,
+    negative_cycle: Option<Vec<NodeIndex>>,
+}
+
+impl Johnson {
+    /// Does a pass of Bellman-Ford from an extra node with an edge to every other node,
+    /// returning a node that was relaxed when it shouldn't have been if there's a negative
+    /// cycle
+    fn relax_potentials(&mut self, graph: &mut Graph) -> Option<usize> {
+        let len = self.matrix.nodes.len();
+        self.passes += 1;
+
+        for a in 0..len {
+            for (b, weight) in self.edges[a].clone() {
+                let potential = self.potentials[a] + weight;
+                if potential >= self.potentials[b] {
+                    continue;
+                }
+
+                self.potentials[b] = potential;
+                self.predecessors[b] = Some(a);
+
+                // Every potential has been found by now, so this edge must be on a negative cycle
+                if self.passes >= len {
+                    return Some(b);
+                }
+
+                let (a, b) = (self.matrix.nodes[a], self.matrix.nodes[b]);
+                graph.set_edge_state(a, b, EdgeState::Relaxed);
+                self.relaxed.push((a, b));
+            }
+        }
+
+        None
+    }
+
+    fn find_cycle(&self, from: usize) -> Vec<NodeIndex> {
+        // Going back once for every node guarantees we end up on the cycle itself
+        let mut on_cycle = from;
+        for _ in 0..self.matrix.nodes.len() {
+            match self.predecessors[on_cycle] {
+                Some(p) => on_cycle = p,
+                None => return Vec::new(),
+            }
+        }
+
+        let mut cycle = vec![on_cycle];
+        let mut curr = self.predecessors[on_cycle];
+        while let Some(p) = curr.filter(|p| *p != on_cycle) {
+            cycle.push(p);
+            curr = self.predecessors[p];
+        }
+
+        cycle.reverse();
+        cycle.into_iter().map(|p| self.matrix.nodes[p]).collect()
+    }
+
+    /// Runs Dijkstra's from `source` with the reweighted edges, filling in its row
+    fn dijkstra(&mut self, source: usize) {
+        let len = self.matrix.nodes.len();
+        let mut distances = vec![f32::INFINITY; len];
+        let mut previous = vec![None; len];
+        let mut done = vec![false; len];
+
+        distances[source] = 0.0;
+        let mut queue = BinaryHeap::from([Queued {
+            priority: 0.0,
+            node: self.matrix.nodes[source],
+        }]);
+
+        while let Some(Queued { priority, node }) = queue.pop() {
+            let a = self.positions[&node];
+            if done[a] {
+                continue;
+            }
+            done[a] = true;
+
+            for (b, weight) in &self.edges[a] {
+                // Rounding can leave a reweighted edge just below zero
+                let weight = (weight + self.potentials[a] - self.potentials[*b]).max(0.0);
+
+                if priority + weight < distances[*b] {
+                    distances[*b] = priority + weight;
+                    previous[*b] = Some(a);
+                    queue.push(Queued {
+                        priority: distances[*b],
+                        node: self.matrix.nodes[*b],
+                    });
+                }
+            }
+        }
+
+        for (b, previous) in previous.into_iter().enumerate() {
+            if let Some(previous) = previous {
+                // Taking the potentials back off gives the distance with the real weights
+                let distance = distances[b] - self.potentials[source] + self.potentials[b];
+                self.matrix.set(source, b, distance, previous);
+            }
+        }
+    }
+}
+
+impl Traverser for Johnson {
+    fn name(&self) -> &'static str {
+        "Johnson's"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_matrix_size(graph, "Johnson's")?;
+
+        let matrix = DistanceMatrix::new(graph);
+        let positions = matrix
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (*n, i))
+            .collect::<HashMap<_, _>>();
+
+        let edges = graph
+            .get_nodes()
+            .iter()
+            .map(|n| {
+                n.get_edges()
+                    .iter()
+                    .map(|e| (positions[&e.get_nodes().1], e.get_weight()))
+                    .collect()
+            })
+            .collect();
+
+        *self = Johnson {
+            potentials: vec![0.0; matrix.nodes.len()],
+            predecessors: vec![None; matrix.nodes.len()],
+            matrix,
+            positions,
+            edges,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        self.matrix.updated.clear();
+        for (a, b) in self.relaxed.drain(..) {
+            graph.set_edge_state(a, b, EdgeState::None);
+        }
+
+        if !self.reweighted {
+            if let Some(relaxed) = self.relax_potentials(graph) {
+                let cycle = self.find_cycle(relaxed);
+                mark_cycle(graph, &cycle);
+                self.negative_cycle = Some(cycle);
+                return;
+            }
+
+            for (idx, potential) in self.matrix.nodes.iter().zip(&self.potentials) {
+                graph
+                    .get_node_mut(*idx)
+                    .set_badge(Some(format!("h {potential}")));
+            }
+
+            self.reweighted = self.relaxed.is_empty();
+            return;
+        }
+
+        let Some(source) = self.matrix.nodes.get(self.sources).copied() else {
+            return;
+        };
+
+        self.dijkstra(self.sources);
+        self.sources += 1;
+
+        graph.get_node_mut(source).visit();
+    }
+
+    fn finished(&self) -> bool {
+        self.negative_cycle.is_some() || self.sources >= self.matrix.nodes.len()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.matrix
+            .nodes
+            .get(self.sources..)
+            .unwrap_or_default()
+            .to_vec()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.matrix.nodes[..self.sources.min(self.matrix.nodes.len())].to_vec()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        if let Some(cycle) = &self.negative_cycle {
+            let message = if cycle.is_empty() {
+                "Found a negative cycle, so some paths can keep getting shorter forever".to_owned()
+            } else {
+                format!(
+                    "Found a negative cycle, so some paths can keep getting shorter forever: {}",
+                    cycle_names(graph, cycle)
+                )
+            };
+
+            ui.colored_label(ui.visuals().error_fg_color, message);
+            return;
+        }
+
+        if self.finished() {
+            ui.label("Found the shortest path between every pair of nodes");
+            eccentricity_ui(ui, graph, &self.matrix);
+        } else if !self.reweighted {
+            ui.label(format!(
+                "Working out potentials with Bellman-Ford, pass {} relaxed {} edges",
+                self.passes,
+                self.relaxed.len()
+            ));
+        } else {
+            ui.label(format!(
+                "Ran Dijkstra's from {} of {} nodes",
+                self.sources,
+                self.matrix.nodes.len()
+            ));
+        }
+    }
+
+    fn distance_matrix(&self) -> Option<&DistanceMatrix> {
+        Some(&self.matrix)
+    }
+}
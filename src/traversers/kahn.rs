@@ -0,0 +1,186 @@
+use std::collections::{HashMap, VecDeque};
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{check_directed, mark_cycle, topological_order_ui, Traverser},
+};
+
+/// Sorts a directed graph by repeatedly taking out nodes with nothing left pointing at them
+///
+/// Each node shows how many of its incoming edges haven't been taken out yet.
+#[derive(Clone, Default)]
+pub struct Kahn {
+    node_count: usize,
+    in_degrees: HashMap<NodeIndex, usize>,
+    /// The nodes with nothing left pointing at them
+    queue: VecDeque<NodeIndex>,
+    order: Vec<NodeIndex>,
+    started: bool,
+    finished: bool,
+    cycle: Option<Vec<NodeIndex>>,
+}
+
+impl Kahn {
+    /// Finds a cycle among the nodes that could never be sorted
+    ///
+    /// Each of them still has an edge coming in from another one, so walking backwards along
+    /// those edges has to come round to a node we've already seen.
+    fn find_cycle(&self, graph: &Graph) -> Vec<NodeIndex> {
+        let blocked = |n: &NodeIndex| self.in_degrees.get(n).copied().unwrap_or(0) > 0;
+
+        let mut predecessors = HashMap::new();
+        for edge in graph.get_edges() {
+            let (a, b) = edge.get_nodes();
+            if blocked(&a) && blocked(&b) {
+                predecessors.insert(b, a);
+            }
+        }
+
+        let Some(mut curr) = graph.get_nodes().iter().map(|n| n.get_id()).find(blocked) else {
+            return Vec::new();
+        };
+
+        let mut walked = Vec::new();
+        while !walked.contains(&curr) {
+            walked.push(curr);
+            curr = predecessors[&curr];
+        }
+
+        let start = walked.iter().position(|n| *n == curr).unwrap_or(0);
+        let mut cycle = walked.split_off(start);
+        cycle.reverse();
+        cycle
+    }
+
+    fn set_badge(&self, graph: &mut Graph, idx: NodeIndex) {
+        graph
+            .get_node_mut(idx)
+            .set_badge(Some(format!("in {}", self.in_degrees[&idx])));
+    }
+}
+
+impl Traverser for Kahn {
+    fn name(&self) -> &'static str {
+        "Kahn's Topological Sort"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_directed(graph, "Topological sorting")?;
+
+        let mut in_degrees = graph
+            .get_nodes()
+            .iter()
+            .map(|n| (n.get_id(), 0))
+            .collect::<HashMap<_, _>>();
+
+        for edge in graph.get_edges() {
+            *in_degrees.entry(edge.get_nodes().1).or_default() += 1;
+        }
+
+        *self = Kahn {
+            node_count: graph.get_nodes().len(),
+            queue: graph
+                .get_nodes()
+                .iter()
+                .map(|n| n.get_id())
+                .filter(|n| in_degrees[n] == 0)
+                .collect(),
+            in_degrees,
+            finished: graph.get_nodes().is_empty(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        if !self.started {
+            self.started = true;
+
+            let nodes = graph
+                .get_nodes()
+                .iter()
+                .map(|n| n.get_id())
+                .collect::<Vec<_>>();
+            for idx in nodes {
+                self.set_badge(graph, idx);
+            }
+
+            for idx in &self.queue {
+                graph.get_node_mut(*idx).view();
+            }
+        } else if let Some(idx) = self.queue.pop_front() {
+            self.order.push(idx);
+
+            let node = graph.get_node_mut(idx);
+            node.visit();
+            node.set_badge(Some(format!("#{}", self.order.len())));
+
+            for edge in node.get_edges() {
+                let next = edge.get_nodes().1;
+                let Some(in_degree) = self.in_degrees.get_mut(&next) else {
+                    continue;
+                };
+
+                *in_degree -= 1;
+                self.set_badge(graph, next);
+
+                if self.in_degrees[&next] == 0 {
+                    graph.get_node_mut(next).view();
+                    self.queue.push_back(next);
+                }
+            }
+
+            self.in_degrees.remove(&idx);
+        }
+
+        if self.queue.is_empty() {
+            self.finished = true;
+
+            if self.order.len() < self.node_count {
+                let cycle = self.find_cycle(graph);
+                mark_cycle(graph, &cycle);
+                self.cycle = Some(cycle);
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.finished
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.queue.iter().copied().collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.order.clone()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        topological_order_ui(ui, graph, &self.order, self.cycle.as_deref(), self.finished);
+    }
+}
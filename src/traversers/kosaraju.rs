@@ -0,0 +1,221 @@
+use std::collections::{HashMap, HashSet};
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{components_ui, condensation, mark_component, Traverser},
+};
+
+/// Finds strongly connected components with two depth first searches
+///
+/// The first works out the order nodes finish in, shown on each node. The second goes
+/// through the nodes in reverse finishing order following edges backwards, and everything
+/// each search reaches is a component.
+#[derive(Clone, Default)]
+pub struct Kosaraju {
+    /// Every node, in the order we try to start the first searches from
+    roots: Vec<NodeIndex>,
+    next_root: usize,
+    /// The nodes being searched, along with how many of their edges have been followed
+    stack: Vec<(NodeIndex, usize)>,
+    seen: HashSet<NodeIndex>,
+    finish_order: Vec<NodeIndex>,
+    /// The nodes each node has edges coming in from
+    incoming: HashMap<NodeIndex, Vec<NodeIndex>>,
+    /// The nodes waiting to be added to the component being built
+    backwards_stack: Vec<NodeIndex>,
+    assigned: HashSet<NodeIndex>,
+    component: Vec<NodeIndex>,
+    components: Vec<Vec<NodeIndex>>,
+}
+
+impl Kosaraju {
+    fn first_pass_done(&self) -> bool {
+        self.stack.is_empty()
+            && self.roots[self.next_root..]
+                .iter()
+                .all(|n| self.seen.contains(n))
+    }
+
+    /// Takes a step of the search that works out the finishing order
+    fn forwards_step(&mut self, graph: &mut Graph) {
+        let Some((idx, mut followed)) = self.stack.last().copied() else {
+            while let Some(root) = self.roots.get(self.next_root).copied() {
+                self.next_root += 1;
+
+                if self.seen.insert(root) {
+                    graph.get_node_mut(root).view();
+                    self.stack.push((root, 0));
+                    return;
+                }
+            }
+            return;
+        };
+
+        let edges = graph.get_node(idx).get_edges();
+
+        let next = loop {
+            let Some(edge) = edges.get(followed) else {
+                break None;
+            };
+            followed += 1;
+
+            let next = edge.get_nodes().1;
+            if !self.seen.contains(&next) {
+                break Some(next);
+            }
+        };
+
+        if let Some(top) = self.stack.last_mut() {
+            top.1 = followed;
+        }
+
+        match next {
+            Some(next) => {
+                self.seen.insert(next);
+                graph.get_node_mut(next).view();
+                self.stack.push((next, 0));
+            }
+            None => {
+                self.stack.pop();
+                self.finish_order.push(idx);
+
+                let node = graph.get_node_mut(idx);
+                node.visit();
+                node.set_badge(Some(format!("fin {}", self.finish_order.len())));
+            }
+        }
+    }
+
+    /// Takes a step of the search along edges backwards that builds up components
+    fn backwards_step(&mut self, graph: &mut Graph) {
+        let Some(idx) = self.backwards_stack.pop() else {
+            if !self.component.is_empty() {
+                let component = std::mem::take(&mut self.component);
+                mark_component(graph, &component, self.components.len());
+                self.components.push(component);
+                return;
+            }
+
+            // The next component starts from whichever node finished last that's left
+            let next = self
+                .finish_order
+                .iter()
+                .rev()
+                .find(|n| !self.assigned.contains(n))
+                .copied();
+
+            if let Some(next) = next {
+                self.add_to_component(graph, next);
+            }
+            return;
+        };
+
+        for n in self.incoming.get(&idx).cloned().unwrap_or_default() {
+            if !self.assigned.contains(&n) {
+                self.add_to_component(graph, n);
+            }
+        }
+    }
+
+    fn add_to_component(&mut self, graph: &mut Graph, idx: NodeIndex) {
+        self.assigned.insert(idx);
+        self.component.push(idx);
+        self.backwards_stack.push(idx);
+
+        graph.get_node_mut(idx).view();
+    }
+}
+
+impl Traverser for Kosaraju {
+    fn name(&self) -> &'static str {
+        "Kosaraju's Strongly Connected Components"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let mut incoming: HashMap<_, Vec<_>> = HashMap::new();
+        for node in graph.get_nodes() {
+            for edge in node.get_edges() {
+                let (a, b) = edge.get_nodes();
+                incoming.entry(b).or_default().push(a);
+            }
+        }
+
+        *self = Kosaraju {
+            roots: graph.get_nodes().iter().map(|n| n.get_id()).collect(),
+            incoming,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        if self.first_pass_done() {
+            self.backwards_step(graph);
+        } else {
+            self.forwards_step(graph);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.first_pass_done()
+            && self.component.is_empty()
+            && self.assigned.len() == self.roots.len()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        if self.first_pass_done() {
+            self.backwards_stack.iter().rev().copied().collect()
+        } else {
+            self.stack.iter().rev().map(|(n, _)| *n).collect()
+        }
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        if self.first_pass_done() {
+            self.assigned.iter().copied().collect()
+        } else {
+            self.finish_order.clone()
+        }
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        if !self.first_pass_done() {
+            ui.label(format!(
+                "Finding the finishing order, {} of {} nodes finished",
+                self.finish_order.len(),
+                self.roots.len()
+            ));
+        }
+
+        components_ui(ui, graph, &self.components, self.finished());
+    }
+
+    fn derived_graph(&self, graph: &Graph) -> Option<Graph> {
+        self.finished()
+            .then(|| condensation(graph, &self.components))
+    }
+}
@@ -0,0 +1,138 @@
+use egui::{ScrollArea, Ui};
+
+use crate::{
+    graph::{Edge, EdgeState, Graph, NodeIndex},
+    traversers::{check_undirected, tree_weight_ui, Traverser, UnionFind},
+};
+
+/// Looks at every edge from lightest to heaviest,
+/// keeping the ones that join two trees together, one edge per step
+#[derive(Clone, Default)]
+pub struct Kruskal {
+    /// Every edge sorted by weight, along with whether it was accepted once it's been looked at
+    edges: Vec<(Edge, Option<bool>)>,
+    next_edge: usize,
+    trees: UnionFind,
+    tree_weight: f32,
+    tree_edges: usize,
+    /// The trees merged by the last edge, named by the nodes representing them
+    last_merge: Option<(NodeIndex, NodeIndex)>,
+}
+
+impl Traverser for Kruskal {
+    fn name(&self) -> &'static str {
+        "Kruskal's Minimum Spanning Tree"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_undirected(graph, "Kruskal's")?;
+
+        let mut edges = graph.get_edges();
+        edges.sort_by(|a, b| a.get_weight().total_cmp(&b.get_weight()));
+
+        *self = Kruskal {
+            edges: edges.into_iter().map(|e| (e, None)).collect(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some((edge, accepted)) = self.edges.get_mut(self.next_edge) else {
+            return;
+        };
+        self.next_edge += 1;
+
+        let (weight, a, b) = edge.get_weighted_nodes();
+        let (tree_a, tree_b) = (self.trees.find(a), self.trees.find(b));
+
+        if self.trees.union(a, b) {
+            *accepted = Some(true);
+            graph.set_single_edge_state(a, b, weight, EdgeState::Accepted);
+            graph.get_node_mut(a).visit();
+            graph.get_node_mut(b).visit();
+
+            self.tree_weight += weight;
+            self.tree_edges += 1;
+            self.last_merge = Some((tree_a, tree_b));
+        } else {
+            *accepted = Some(false);
+            graph.set_single_edge_state(a, b, weight, EdgeState::Rejected);
+            self.last_merge = None;
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.next_edge >= self.edges.len()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.edges
+            .get(self.next_edge)
+            .map(|(e, _)| {
+                let (a, b) = e.get_nodes();
+                vec![a, b]
+            })
+            .unwrap_or_default()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.edges[..self.next_edge]
+            .iter()
+            .filter(|(_, accepted)| *accepted == Some(true))
+            .flat_map(|(e, _)| {
+                let (a, b) = e.get_nodes();
+                [a, b]
+            })
+            .collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        tree_weight_ui(ui, self.tree_weight, self.tree_edges, self.finished());
+
+        if let Some((a, b)) = self.last_merge {
+            ui.label(format!(
+                "Merged the trees containing {} and {}",
+                graph.get_node(a).get_name(),
+                graph.get_node(b).get_name()
+            ));
+        }
+
+        ui.label("Edges by weight");
+        ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+            for (i, (edge, accepted)) in self.edges.iter().enumerate() {
+                let (weight, a, b) = edge.get_weighted_nodes();
+                let status = match accepted {
+                    Some(true) => "accepted",
+                    Some(false) => "rejected",
+                    None if i == self.next_edge => "next",
+                    None => "",
+                };
+
+                ui.monospace(format!(
+                    "{} - {} ({weight}) {status}",
+                    graph.get_node(a).get_name(),
+                    graph.get_node(b).get_name()
+                ));
+            }
+        });
+    }
+}
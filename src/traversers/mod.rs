@@ -0,0 +1,643 @@
+pub mod all_pairs;
+pub mod astar;
+pub mod backtracking_coloring;
+pub mod bellman_ford;
+pub mod bidirectional;
+pub mod bipartite;
+pub mod boruvka;
+pub mod breadth_first;
+pub mod bridges;
+pub mod coloring;
+pub mod depth_first;
+pub mod dijkstra;
+pub mod dinic;
+pub mod dsatur;
+pub mod edmonds_karp;
+pub mod eulerian;
+pub mod flow;
+pub mod floyd_warshall;
+pub mod greedy_best_first;
+pub mod greedy_coloring;
+pub mod hopcroft_karp;
+pub mod iterative_deepening;
+pub mod johnson;
+pub mod kahn;
+pub mod kosaraju;
+pub mod kruskal;
+pub mod prim;
+pub mod tarjan;
+pub mod topological_dfs;
+pub mod welsh_powell;
+
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+};
+
+use egui::{ScrollArea, Ui};
+use instant::Instant;
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex, NodeTraversal},
+    traversers::{
+        all_pairs::DistanceMatrix, astar::AStar, backtracking_coloring::BacktrackingColoring,
+        bellman_ford::BellmanFord, bidirectional::BidirectionalSearch, bipartite::BipartiteCheck,
+        boruvka::Boruvka, breadth_first::BreadthFirst, bridges::Bridges, depth_first::DepthFirst,
+        dijkstra::Dijkstra, dinic::Dinic, dsatur::DSatur, edmonds_karp::EdmondsKarp,
+        eulerian::Eulerian, floyd_warshall::FloydWarshall, greedy_best_first::GreedyBestFirst,
+        greedy_coloring::GreedyColoring, hopcroft_karp::HopcroftKarp,
+        iterative_deepening::IterativeDeepening, johnson::Johnson, kahn::Kahn, kosaraju::Kosaraju,
+        kruskal::Kruskal, prim::Prim, tarjan::Tarjan, topological_dfs::TopologicalDfs,
+        welsh_powell::WelshPowell,
+    },
+};
+
+pub const TRAVERSER_COUNT: usize = 27;
+
+pub fn traversers() -> [Box<dyn Traverser>; TRAVERSER_COUNT] {
+    [
+        Box::new(DepthFirst::default()),
+        Box::new(BreadthFirst::default()),
+        Box::new(BidirectionalSearch::default()),
+        Box::new(IterativeDeepening::default()),
+        Box::new(Dijkstra::default()),
+        Box::new(AStar::default()),
+        Box::new(GreedyBestFirst::default()),
+        Box::new(BellmanFord::default()),
+        Box::new(FloydWarshall::default()),
+        Box::new(Johnson::default()),
+        Box::new(Prim::default()),
+        Box::new(Kruskal::default()),
+        Box::new(Boruvka::default()),
+        Box::new(Kahn::default()),
+        Box::new(TopologicalDfs::default()),
+        Box::new(Tarjan::default()),
+        Box::new(Kosaraju::default()),
+        Box::new(Bridges::default()),
+        Box::new(EdmondsKarp::default()),
+        Box::new(Dinic::default()),
+        Box::new(BipartiteCheck::default()),
+        Box::new(HopcroftKarp::default()),
+        Box::new(GreedyColoring::default()),
+        Box::new(WelshPowell::default()),
+        Box::new(DSatur::default()),
+        Box::new(BacktrackingColoring::default()),
+        Box::new(Eulerian::default()),
+    ]
+}
+
+pub trait Traverser {
+    fn name(&self) -> &'static str;
+    /// Gets ready to traverse from `start`, or explains why we can't
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        end: Option<NodeIndex>,
+    ) -> Result<(), String>;
+    fn step(&mut self, graph: &mut Graph);
+    fn finished(&self) -> bool;
+    /// The nodes waiting to be visited, in the order they'll be visited
+    fn frontier(&self) -> Vec<NodeIndex>;
+    fn visited(&self) -> Vec<NodeIndex>;
+    /// Copies the traverser and all of its state, used to step backwards
+    fn box_clone(&self) -> Box<dyn Traverser>;
+
+    /// Whether the traversal starts from a particular node, if not we don't ask for one
+    fn uses_start_node(&self) -> bool {
+        true
+    }
+
+    /// Whether the traversal is looking for an end node, if not we don't ask for one
+    fn uses_end_node(&self) -> bool {
+        true
+    }
+
+    /// Whether the path to the end node should be drawn once we're finished
+    fn shows_path(&self) -> bool {
+        true
+    }
+
+    /// How many nodes have been taken off the frontier and had their neighbors looked at
+    fn nodes_expanded(&self) -> usize {
+        self.visited().len()
+    }
+
+    /// Shows the options the traversal can be started with
+    #[allow(unused)]
+    fn settings_ui(&mut self, ui: &mut Ui) {}
+
+    /// Shows what the traversal has found so far
+    #[allow(unused)]
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {}
+
+    /// A new graph built out of what the traversal found, which can be added to the app
+    #[allow(unused)]
+    fn derived_graph(&self, graph: &Graph) -> Option<Graph> {
+        None
+    }
+
+    /// The distances between every pair of nodes, for traversals that find all of them
+    fn distance_matrix(&self) -> Option<&DistanceMatrix> {
+        None
+    }
+}
+
+/// A node waiting in a [`BinaryHeap`](std::collections::BinaryHeap)
+///
+/// These are ordered backwards so the lowest priority comes out first.
+#[derive(Clone, Copy)]
+pub struct Queued {
+    pub priority: f32,
+    pub node: NodeIndex,
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Queued {}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.total_cmp(&self.priority)
+    }
+}
+
+/// Colors a node that's being visited, using the start and end colors where they apply
+pub fn mark_visited(
+    graph: &mut Graph,
+    idx: NodeIndex,
+    start: Option<NodeIndex>,
+    end: Option<NodeIndex>,
+) {
+    let node = graph.get_node_mut(idx);
+
+    if Some(idx) == end {
+        node.end();
+    } else if Some(idx) == start {
+        node.start();
+    } else {
+        node.visit();
+    }
+}
+
+/// Gets the start node for traversers that need one
+pub fn require_start(start: Option<NodeIndex>) -> Result<NodeIndex, String> {
+    start.ok_or_else(|| "Pick a node to start from".to_owned())
+}
+
+/// Explains why a traverser can't be used if the graph has any negative edges
+pub fn check_no_negative_edges(graph: &Graph, name: &str) -> Result<(), String> {
+    if graph.get_edges().iter().any(|e| e.get_weight() < 0.0) {
+        Err(format!(
+            "{name} can't handle negative edge weights, try Bellman-Ford instead"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Explains why a traverser can't be used if the graph is directed
+pub fn check_undirected(graph: &Graph, name: &str) -> Result<(), String> {
+    if graph.is_directed() {
+        Err(format!("{name} only works on undirected graphs"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Explains why a traverser can't be used if the graph is undirected
+pub fn check_directed(graph: &Graph, name: &str) -> Result<(), String> {
+    if graph.is_directed() {
+        Ok(())
+    } else {
+        Err(format!("{name} only works on directed graphs"))
+    }
+}
+
+/// Paints the edges of a cycle, given its nodes in order
+pub fn mark_cycle(graph: &mut Graph, cycle: &[NodeIndex]) {
+    for (i, n) in cycle.iter().enumerate() {
+        let next = cycle[(i + 1) % cycle.len()];
+        graph.set_edge_state(*n, next, EdgeState::Cycle);
+    }
+}
+
+/// Writes out a cycle's node names, going back round to the first one at the end
+pub fn cycle_names(graph: &Graph, cycle: &[NodeIndex]) -> String {
+    let mut names = cycle
+        .iter()
+        .map(|n| graph.get_node(*n).get_name())
+        .collect::<Vec<_>>();
+    names.extend(names.first().copied());
+
+    names.join(" → ")
+}
+
+/// Shows the order a topological sort has come up with, or the cycle that got in its way
+pub fn topological_order_ui(
+    ui: &mut Ui,
+    graph: &Graph,
+    order: &[NodeIndex],
+    cycle: Option<&[NodeIndex]>,
+    finished: bool,
+) {
+    if let Some(cycle) = cycle {
+        ui.colored_label(
+            ui.visuals().error_fg_color,
+            format!(
+                "There's no topological order because of the cycle {}",
+                cycle_names(graph, cycle)
+            ),
+        );
+        return;
+    }
+
+    if finished {
+        ui.label("Topological order");
+    } else {
+        ui.label(format!(
+            "Sorted {} of {} nodes",
+            order.len(),
+            graph.get_nodes().len()
+        ));
+    }
+
+    ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+        for (i, idx) in order.iter().enumerate() {
+            ui.monospace(format!("{}. {}", i + 1, graph.get_node(*idx).get_name()));
+        }
+    });
+}
+
+/// Paints a finished component in its own color
+pub fn mark_component(graph: &mut Graph, component: &[NodeIndex], group: usize) {
+    for idx in component {
+        let node = graph.get_node_mut(*idx);
+        node.visit();
+        node.set_group(Some(group));
+    }
+}
+
+/// Lists the strongly connected components found so far
+pub fn components_ui(ui: &mut Ui, graph: &Graph, components: &[Vec<NodeIndex>], finished: bool) {
+    if finished {
+        ui.label(format!(
+            "Found {} strongly connected components",
+            components.len()
+        ));
+    } else {
+        ui.label(format!("Components so far: {}", components.len()));
+    }
+
+    ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+        for (i, component) in components.iter().enumerate() {
+            ui.monospace(format!("{}. {}", i + 1, component_name(graph, component)));
+        }
+    });
+}
+
+fn component_name(graph: &Graph, component: &[NodeIndex]) -> String {
+    let names = component
+        .iter()
+        .map(|n| graph.get_node(*n).get_name())
+        .collect::<Vec<_>>();
+
+    format!("{{{}}}", names.join(", "))
+}
+
+/// Builds the condensation of a graph, where each strongly connected component becomes a
+/// single node sitting in the middle of the nodes it replaces
+pub fn condensation(graph: &Graph, components: &[Vec<NodeIndex>]) -> Graph {
+    let mut condensed = Graph::new(
+        format!("{} Condensation", graph.get_name()),
+        graph.is_directed(),
+        false,
+    );
+
+    let mut component_of = HashMap::new();
+    let mut new_nodes = Vec::new();
+
+    for (i, component) in components.iter().enumerate() {
+        let (x, y) = component
+            .iter()
+            .map(|n| graph.get_node(*n).get_pos())
+            .fold((0.0, 0.0), |(x, y), (nx, ny)| (x + nx, y + ny));
+        let len = component.len().max(1) as f32;
+
+        new_nodes.push(condensed.add_node(
+            (x / len, y / len),
+            component_name(graph, component),
+            Vec::new(),
+        ));
+
+        for idx in component {
+            component_of.insert(*idx, i);
+        }
+    }
+
+    let mut added = HashSet::new();
+    for edge in graph.get_edges() {
+        let (a, b) = edge.get_nodes();
+        let (Some(a), Some(b)) = (component_of.get(&a), component_of.get(&b)) else {
+            continue;
+        };
+
+        if a != b && added.insert((*a, *b)) {
+            condensed.add_edge(new_nodes[*a], new_nodes[*b], None);
+        }
+    }
+
+    condensed
+}
+
+/// Keeps track of which nodes have been joined together
+#[derive(Clone, Default)]
+pub struct UnionFind {
+    parents: HashMap<NodeIndex, NodeIndex>,
+    /// How many nodes are in each set, only kept for the node representing it
+    sizes: HashMap<NodeIndex, usize>,
+}
+
+impl UnionFind {
+    /// Finds the node representing the set `idx` is in
+    pub fn find(&self, mut idx: NodeIndex) -> NodeIndex {
+        while let Some(parent) = self.parents.get(&idx) {
+            idx = *parent;
+        }
+        idx
+    }
+
+    /// Joins the sets `a` and `b` are in, returning false if they were already the same set
+    pub fn union(&mut self, a: NodeIndex, b: NodeIndex) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        // Hanging the smaller set off the bigger one keeps lookups short
+        let size = |n| self.sizes.get(&n).copied().unwrap_or(1);
+        let (big, small) = if size(a) >= size(b) { (a, b) } else { (b, a) };
+
+        let merged = size(big) + size(small);
+        self.parents.insert(small, big);
+        self.sizes.remove(&small);
+        self.sizes.insert(big, merged);
+
+        true
+    }
+}
+
+/// Shows the weight of the tree a spanning tree algorithm has built so far
+pub fn tree_weight_ui(ui: &mut Ui, weight: f32, edges: usize, finished: bool) {
+    if finished {
+        ui.label(format!(
+            "Minimum spanning tree weight: {weight} ({edges} edges)"
+        ));
+    } else {
+        ui.label(format!("Tree weight so far: {weight} ({edges} edges)"));
+    }
+}
+
+/// Finds every node connected to `start`, ignoring which way edges go
+pub fn connected_nodes(graph: &Graph, start: NodeIndex) -> HashSet<NodeIndex> {
+    let mut found = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(idx) = queue.pop_front() {
+        for n in graph.get_connections(idx) {
+            if found.insert(n) {
+                queue.push_back(n);
+            }
+        }
+    }
+
+    found
+}
+
+/// How many steps apart copies of the traverser are kept, stepping back replays up to this many
+/// steps from the copy before it
+const CHECKPOINT_INTERVAL: usize = 64;
+
+pub struct TraversalManager {
+    pub last_traversal: Instant,
+    pub speed: u32,
+    pub auto: bool,
+    pub currently_traversing: bool,
+    pub traversal: Option<Box<dyn Traverser>>,
+    pub end_node: Option<NodeIndex>,
+    /// The pair of nodes picked from the distance matrix, whose path gets drawn
+    pub selected_pair: Option<(NodeIndex, NodeIndex)>,
+    /// The steps taken so far, most recent last
+    steps: Vec<StepDelta>,
+    /// The traverser as it was every [`CHECKPOINT_INTERVAL`] steps, starting from before the
+    /// first step
+    checkpoints: Vec<Box<dyn Traverser>>,
+    /// The most steps that have been taken, including ones that have been stepped back over
+    known_steps: usize,
+}
+
+/// Each node a single step changed, with how it looked before and after
+///
+/// The traverser itself isn't kept, it's copied every so often and stepped forward from the
+/// copy instead.
+struct StepDelta {
+    nodes: Vec<(NodeIndex, NodeTraversal, NodeTraversal)>,
+}
+
+impl TraversalManager {
+    /// Starts traversing `graph` with `traverser`, or explains why it can't
+    pub fn new_traversal(
+        &mut self,
+        mut traverser: Box<dyn Traverser>,
+        graph: &mut Graph,
+        start_node: Option<NodeIndex>,
+        end_node: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        graph.reset();
+        traverser.init(graph, start_node, end_node)?;
+
+        self.currently_traversing = !traverser.finished();
+        self.checkpoints = vec![traverser.box_clone()];
+        self.traversal = Some(traverser);
+        self.end_node = end_node;
+        self.selected_pair = None;
+        self.steps.clear();
+        self.known_steps = 0;
+
+        Ok(())
+    }
+
+    pub fn update(&mut self, graph: &mut Graph) {
+        if self.currently_traversing {
+            if self.auto {
+                let now = Instant::now();
+                if let Some(dur) = now.checked_duration_since(self.last_traversal) {
+                    if dur.as_millis() as u32 >= self.speed {
+                        self.step_forward(graph);
+                        self.last_traversal = now;
+                    }
+                }
+            } else {
+                self.step_forward(graph);
+            }
+        }
+    }
+
+    /// The node whose path should be shown, once a traversal has finished
+    pub fn path_end(&self) -> Option<NodeIndex> {
+        match &self.traversal {
+            Some(traversal) if !self.currently_traversing && traversal.shows_path() => {
+                self.end_node
+            }
+            _ => None,
+        }
+    }
+
+    /// The shortest path found so far between the pair picked from the distance matrix
+    pub fn selected_path(&self) -> Option<Vec<NodeIndex>> {
+        let (a, b) = self.selected_pair?;
+        self.traversal.as_ref()?.distance_matrix()?.path(a, b)
+    }
+
+    pub fn stop_traversal(&mut self) {
+        self.traversal = None;
+        self.selected_pair = None;
+        self.steps.clear();
+        self.checkpoints.clear();
+        self.known_steps = 0;
+    }
+
+    /// How many steps have been taken
+    pub fn current_step(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// How many steps we know about, including ones that have been stepped back over
+    pub fn known_steps(&self) -> usize {
+        self.known_steps
+    }
+
+    /// Undoes the last step, returning false if there wasn't one
+    pub fn step_back(&mut self, graph: &mut Graph) -> bool {
+        if self.traversal.is_none() || self.steps.is_empty() {
+            return false;
+        }
+
+        self.rewind(self.steps.len() - 1, graph);
+        true
+    }
+
+    /// Goes back to `step` by undoing the changes to the nodes as far as the checkpoint before
+    /// it, then stepping forward again from a copy of the checkpoint
+    fn rewind(&mut self, step: usize, graph: &mut Graph) {
+        let checkpoint = (step / CHECKPOINT_INTERVAL).min(self.checkpoints.len() - 1);
+
+        while self.steps.len() > checkpoint * CHECKPOINT_INTERVAL {
+            let Some(delta) = self.steps.pop() else {
+                break;
+            };
+
+            for (idx, before, _) in delta.nodes {
+                if let Some(node) = graph.try_get_node_mut(idx) {
+                    node.set_traversal(before);
+                }
+            }
+        }
+
+        // Later checkpoints get taken again as we step forward
+        self.checkpoints.truncate(checkpoint + 1);
+        let traversal = self.checkpoints[checkpoint].box_clone();
+        self.currently_traversing = !traversal.finished();
+        self.traversal = Some(traversal);
+
+        while self.steps.len() < step && self.currently_traversing {
+            self.step_forward(graph);
+        }
+    }
+
+    /// Takes a single step, whether or not we're traversing automatically
+    pub fn step_forward(&mut self, graph: &mut Graph) {
+        if !self.currently_traversing {
+            return;
+        }
+
+        let Some(traversal) = &mut self.traversal else {
+            return;
+        };
+
+        let nodes_before = graph
+            .get_nodes()
+            .iter()
+            .map(|n| (n.get_id(), n.traversal()))
+            .collect::<Vec<_>>();
+
+        traversal.step(graph);
+
+        let nodes = nodes_before
+            .into_iter()
+            .filter_map(|(idx, before)| {
+                let after = graph.try_get_node(idx)?.traversal();
+                (before != after).then_some((idx, before, after))
+            })
+            .collect();
+
+        self.currently_traversing = !traversal.finished();
+        self.steps.push(StepDelta { nodes });
+        self.known_steps = self.known_steps.max(self.steps.len());
+
+        if self.steps.len() == self.checkpoints.len() * CHECKPOINT_INTERVAL {
+            self.checkpoints.push(traversal.box_clone());
+        }
+    }
+
+    pub fn jump_to_start(&mut self, graph: &mut Graph) {
+        if self.traversal.is_some() {
+            self.rewind(0, graph);
+        }
+    }
+
+    pub fn jump_to_end(&mut self, graph: &mut Graph) {
+        while self.currently_traversing {
+            self.step_forward(graph);
+        }
+    }
+
+    /// Steps backwards or forwards until `step` steps have been taken
+    pub fn seek(&mut self, step: usize, graph: &mut Graph) {
+        if step < self.current_step() && self.traversal.is_some() {
+            self.rewind(step, graph);
+        }
+
+        while self.current_step() < step && self.currently_traversing {
+            self.step_forward(graph);
+        }
+    }
+}
+
+impl Default for TraversalManager {
+    fn default() -> Self {
+        TraversalManager {
+            last_traversal: Instant::now(),
+            speed: 30,
+            auto: true,
+            currently_traversing: false,
+            traversal: None,
+            end_node: None,
+            selected_pair: None,
+            steps: Vec::new(),
+            checkpoints: Vec::new(),
+            known_steps: 0,
+        }
+    }
+}
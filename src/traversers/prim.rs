@@ -0,0 +1,148 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+};
+
+use egui::Ui;
+
+use crate::{
+    graph::{EdgeState, Graph, NodeIndex},
+    traversers::{check_undirected, require_start, tree_weight_ui, Traverser},
+};
+
+/// An edge waiting to be looked at, ordered so the lightest comes out first
+#[derive(Clone, Copy)]
+struct QueuedEdge {
+    weight: f32,
+    from: NodeIndex,
+    to: NodeIndex,
+}
+
+impl PartialEq for QueuedEdge {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedEdge {}
+
+impl PartialOrd for QueuedEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedEdge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.weight.total_cmp(&self.weight)
+    }
+}
+
+/// Grows a minimum spanning tree out from the start node, one edge per step
+#[derive(Clone, Default)]
+pub struct Prim {
+    queue: BinaryHeap<QueuedEdge>,
+    in_tree: HashSet<NodeIndex>,
+    tree_weight: f32,
+    tree_edges: usize,
+    started: Option<NodeIndex>,
+}
+
+impl Prim {
+    fn add_to_tree(&mut self, graph: &mut Graph, idx: NodeIndex) {
+        self.in_tree.insert(idx);
+
+        for edge in graph.get_node(idx).get_edges() {
+            let (weight, _, to) = edge.get_weighted_nodes();
+
+            if !self.in_tree.contains(&to) {
+                graph.get_node_mut(to).view();
+                self.queue.push(QueuedEdge {
+                    weight,
+                    from: idx,
+                    to,
+                });
+            }
+        }
+    }
+}
+
+impl Traverser for Prim {
+    fn name(&self) -> &'static str {
+        "Prim's Minimum Spanning Tree"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let start = require_start(start)?;
+        check_undirected(graph, "Prim's")?;
+
+        *self = Prim {
+            started: Some(start),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        if let Some(start) = self.started.take() {
+            graph.get_node_mut(start).start();
+            self.add_to_tree(graph, start);
+            return;
+        }
+
+        let Some(QueuedEdge { weight, from, to }) = self.queue.pop() else {
+            return;
+        };
+
+        if self.in_tree.contains(&to) {
+            graph.set_single_edge_state(from, to, weight, EdgeState::Rejected);
+            return;
+        }
+
+        graph.set_single_edge_state(from, to, weight, EdgeState::Accepted);
+        graph.get_node_mut(to).visit();
+        self.tree_weight += weight;
+        self.tree_edges += 1;
+
+        self.add_to_tree(graph, to);
+    }
+
+    fn finished(&self) -> bool {
+        self.started.is_none() && self.queue.is_empty()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        let mut queue = self.queue.clone().into_sorted_vec();
+        // The heap is ordered backwards so the sorted queue is too
+        queue.reverse();
+
+        let mut seen = HashSet::new();
+        queue
+            .into_iter()
+            .map(|q| q.to)
+            .filter(|n| !self.in_tree.contains(n) && seen.insert(*n))
+            .collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.in_tree.iter().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, _graph: &Graph, ui: &mut Ui) {
+        tree_weight_ui(ui, self.tree_weight, self.tree_edges, self.finished());
+    }
+}
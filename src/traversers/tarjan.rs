@@ -0,0 +1,180 @@
+use std::collections::{HashMap, HashSet};
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{components_ui, condensation, mark_component, Traverser},
+};
+
+/// Finds strongly connected components in a single depth first search
+///
+/// Each node shows the order it was discovered in and the lowest discovery number it can
+/// get back to. When those match the node is the root of a component, which is everything
+/// above it on the stack.
+#[derive(Clone, Default)]
+pub struct Tarjan {
+    /// Every node, in the order we try to start searches from
+    roots: Vec<NodeIndex>,
+    next_root: usize,
+    /// The nodes being searched, along with how many of their edges have been followed
+    call_stack: Vec<(NodeIndex, usize)>,
+    discovered: HashMap<NodeIndex, usize>,
+    low_links: HashMap<NodeIndex, usize>,
+    /// Nodes that have been discovered but aren't in a component yet
+    stack: Vec<NodeIndex>,
+    on_stack: HashSet<NodeIndex>,
+    components: Vec<Vec<NodeIndex>>,
+}
+
+impl Tarjan {
+    fn discover(&mut self, graph: &mut Graph, idx: NodeIndex) {
+        let number = self.discovered.len();
+        self.discovered.insert(idx, number);
+        self.low_links.insert(idx, number);
+        self.stack.push(idx);
+        self.on_stack.insert(idx);
+        self.call_stack.push((idx, 0));
+
+        graph.get_node_mut(idx).view();
+        self.set_badge(graph, idx);
+    }
+
+    fn lower_link(&mut self, graph: &mut Graph, idx: NodeIndex, to: usize) {
+        if let Some(low) = self.low_links.get_mut(&idx) {
+            if to < *low {
+                *low = to;
+                self.set_badge(graph, idx);
+            }
+        }
+    }
+
+    fn set_badge(&self, graph: &mut Graph, idx: NodeIndex) {
+        graph.get_node_mut(idx).set_badge(Some(format!(
+            "{}/{}",
+            self.discovered[&idx], self.low_links[&idx]
+        )));
+    }
+}
+
+impl Traverser for Tarjan {
+    fn name(&self) -> &'static str {
+        "Tarjan's Strongly Connected Components"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        *self = Tarjan {
+            roots: graph.get_nodes().iter().map(|n| n.get_id()).collect(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some((idx, mut followed)) = self.call_stack.last().copied() else {
+            while let Some(root) = self.roots.get(self.next_root).copied() {
+                self.next_root += 1;
+
+                if !self.discovered.contains_key(&root) {
+                    self.discover(graph, root);
+                    return;
+                }
+            }
+            return;
+        };
+
+        let edges = graph.get_node(idx).get_edges();
+
+        // Edges back to nodes we've already found only update the low link, so they don't get
+        // a step of their own
+        let next = loop {
+            let Some(edge) = edges.get(followed) else {
+                break None;
+            };
+            followed += 1;
+
+            let next = edge.get_nodes().1;
+            match self.discovered.get(&next) {
+                None => break Some(next),
+                Some(number) if self.on_stack.contains(&next) => {
+                    self.lower_link(graph, idx, *number)
+                }
+                Some(_) => {}
+            }
+        };
+
+        if let Some(top) = self.call_stack.last_mut() {
+            top.1 = followed;
+        }
+
+        if let Some(next) = next {
+            self.discover(graph, next);
+            return;
+        }
+
+        self.call_stack.pop();
+
+        let low = self.low_links[&idx];
+        if let Some((parent, _)) = self.call_stack.last().copied() {
+            self.lower_link(graph, parent, low);
+        }
+
+        if low == self.discovered[&idx] {
+            let from = self.stack.iter().position(|n| *n == idx).unwrap_or(0);
+            let component = self.stack.split_off(from);
+
+            for n in &component {
+                self.on_stack.remove(n);
+            }
+
+            mark_component(graph, &component, self.components.len());
+            self.components.push(component);
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.call_stack.is_empty()
+            && self.roots[self.next_root..]
+                .iter()
+                .all(|n| self.discovered.contains_key(n))
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.call_stack.iter().rev().map(|(n, _)| *n).collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.components.iter().flatten().copied().collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        components_ui(ui, graph, &self.components, self.finished());
+    }
+
+    fn derived_graph(&self, graph: &Graph) -> Option<Graph> {
+        self.finished()
+            .then(|| condensation(graph, &self.components))
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{check_directed, mark_cycle, topological_order_ui, Traverser},
+};
+
+/// Sorts a directed graph by the order nodes finish in a depth first search, reversed
+///
+/// Each finished node shows when it finished, and running into a node that's still on the
+/// stack means there's a cycle.
+#[derive(Clone, Default)]
+pub struct TopologicalDfs {
+    /// Every node, in the order we try to start searches from
+    roots: Vec<NodeIndex>,
+    next_root: usize,
+    /// The nodes being searched, along with how many of their edges have been followed
+    stack: Vec<(NodeIndex, usize)>,
+    finish_order: Vec<NodeIndex>,
+    finished_nodes: HashSet<NodeIndex>,
+    cycle: Option<Vec<NodeIndex>>,
+}
+
+impl TopologicalDfs {
+    /// The nodes sorted so far, which is everything that's finished backwards
+    fn order(&self) -> Vec<NodeIndex> {
+        self.finish_order.iter().rev().copied().collect()
+    }
+}
+
+impl Traverser for TopologicalDfs {
+    fn name(&self) -> &'static str {
+        "Depth First Topological Sort"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        check_directed(graph, "Topological sorting")?;
+
+        *self = TopologicalDfs {
+            roots: graph.get_nodes().iter().map(|n| n.get_id()).collect(),
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        let Some((idx, mut followed)) = self.stack.last().copied() else {
+            // Start a new search from the next node nothing has reached yet
+            while let Some(root) = self.roots.get(self.next_root).copied() {
+                self.next_root += 1;
+
+                if !self.finished_nodes.contains(&root) {
+                    graph.get_node_mut(root).view();
+                    self.stack.push((root, 0));
+                    return;
+                }
+            }
+            return;
+        };
+
+        let edges = graph.get_node(idx).get_edges();
+
+        // Edges to nodes that are already done are skipped, there's nothing to show for them
+        let next = loop {
+            let Some(edge) = edges.get(followed) else {
+                break None;
+            };
+            followed += 1;
+
+            let next = edge.get_nodes().1;
+            if !self.finished_nodes.contains(&next) {
+                break Some(next);
+            }
+        };
+
+        if let Some(top) = self.stack.last_mut() {
+            top.1 = followed;
+        }
+
+        let Some(next) = next else {
+            self.stack.pop();
+            self.finished_nodes.insert(idx);
+            self.finish_order.push(idx);
+
+            let node = graph.get_node_mut(idx);
+            node.visit();
+            node.set_badge(Some(format!("fin {}", self.finish_order.len())));
+            return;
+        };
+
+        if let Some(from) = self.stack.iter().position(|(n, _)| *n == next) {
+            let cycle = self.stack[from..]
+                .iter()
+                .map(|(n, _)| *n)
+                .collect::<Vec<_>>();
+
+            mark_cycle(graph, &cycle);
+            self.cycle = Some(cycle);
+            return;
+        }
+
+        graph.get_node_mut(next).view();
+        self.stack.push((next, 0));
+    }
+
+    fn finished(&self) -> bool {
+        self.cycle.is_some()
+            || (self.stack.is_empty()
+                && self.roots[self.next_root..]
+                    .iter()
+                    .all(|n| self.finished_nodes.contains(n)))
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.stack.iter().rev().map(|(n, _)| *n).collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.finish_order.clone()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        topological_order_ui(
+            ui,
+            graph,
+            &self.order(),
+            self.cycle.as_deref(),
+            self.finished(),
+        );
+    }
+}
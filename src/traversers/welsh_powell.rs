@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use egui::Ui;
+
+use crate::{
+    graph::{Graph, NodeIndex},
+    traversers::{
+        coloring::{adjacency, color_node, coloring_ui, largest_first},
+        Traverser,
+    },
+};
+
+/// Colors nodes one color at a time, going down the nodes from the most neighbors to the
+/// fewest and giving the current color to every node that isn't next to one that already has it
+#[derive(Clone, Default)]
+pub struct WelshPowell {
+    order: Vec<NodeIndex>,
+    adjacency: HashMap<NodeIndex, Vec<NodeIndex>>,
+    colors: HashMap<NodeIndex, usize>,
+    /// The color being handed out in this pass down the nodes
+    color: usize,
+    /// How far down the nodes this pass has got
+    next: usize,
+}
+
+impl Traverser for WelshPowell {
+    fn name(&self) -> &'static str {
+        "Welsh-Powell Coloring"
+    }
+
+    fn init(
+        &mut self,
+        graph: &Graph,
+        _start: Option<NodeIndex>,
+        _end: Option<NodeIndex>,
+    ) -> Result<(), String> {
+        let adjacency = adjacency(graph);
+
+        *self = WelshPowell {
+            order: largest_first(graph, &adjacency),
+            adjacency,
+            ..Default::default()
+        };
+
+        Ok(())
+    }
+
+    fn step(&mut self, graph: &mut Graph) {
+        while !self.finished() {
+            let Some(idx) = self.order.get(self.next).copied() else {
+                // Back to the top with the next color
+                self.color += 1;
+                self.next = 0;
+                continue;
+            };
+            self.next += 1;
+
+            let blocked = self.adjacency[&idx]
+                .iter()
+                .any(|n| self.colors.get(n) == Some(&self.color));
+
+            if !self.colors.contains_key(&idx) && !blocked {
+                self.colors.insert(idx, self.color);
+                color_node(graph, idx, self.color);
+                return;
+            }
+        }
+    }
+
+    fn finished(&self) -> bool {
+        self.colors.len() == self.order.len()
+    }
+
+    fn frontier(&self) -> Vec<NodeIndex> {
+        self.order
+            .iter()
+            .filter(|n| !self.colors.contains_key(n))
+            .copied()
+            .collect()
+    }
+
+    fn visited(&self) -> Vec<NodeIndex> {
+        self.order
+            .iter()
+            .filter(|n| self.colors.contains_key(n))
+            .copied()
+            .collect()
+    }
+
+    fn box_clone(&self) -> Box<dyn Traverser> {
+        Box::new(self.clone())
+    }
+
+    fn uses_start_node(&self) -> bool {
+        false
+    }
+
+    fn uses_end_node(&self) -> bool {
+        false
+    }
+
+    fn shows_path(&self) -> bool {
+        false
+    }
+
+    fn results_ui(&self, graph: &Graph, ui: &mut Ui) {
+        if !self.finished() {
+            ui.label(format!("Handing out color {}", self.color + 1));
+        }
+
+        coloring_ui(ui, graph, &self.colors, self.finished());
+    }
+}